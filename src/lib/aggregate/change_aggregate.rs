@@ -1,7 +1,7 @@
 use crate::replication::{EventMetadata};
 use crate::MODIFIED_FIELDS_BUFFER_SIZE;
 
-use serde_json::Value;
+use serde_json::{json, Value};
 use smallvec::SmallVec;
 use std::collections::{HashSet};
 use std::sync::Arc;
@@ -9,15 +9,20 @@ use std::sync::Arc;
 #[derive(Debug, PartialEq)]
 pub enum ChangeAggregateKey {
     Attribute(usize),
+    AttributeAndScopeInt(usize, usize),
+    AttributeCode(Arc<str>, usize),
+    AttributeCodeAndScopeInt(Arc<str>, usize, usize),
     Key(&'static str),
     KeyAndScopeInt(&'static str, usize),
     KeyAndScopeStr(&'static str, &'static str),
+    PathAndScopeInt(Arc<str>, &'static str, usize),
 }
 
 #[derive(Debug, PartialEq)]
 pub enum ChangeAggregateValue {
     Id(Vec<usize>),
     String(Vec<Arc<str>>),
+    Change(Option<Arc<str>>, Option<Arc<str>>),
 }
 
 impl Into<Value> for ChangeAggregateValue {
@@ -27,6 +32,10 @@ impl Into<Value> for ChangeAggregateValue {
             Self::String(value) => {
                 Value::Array(value.into_iter().map(|v| v.to_string().into()).collect())
             }
+            Self::Change(old, new) => json!({
+                "old": old.map(|v| v.to_string()),
+                "new": new.map(|v| v.to_string())
+            }),
         }
     }
 }
@@ -53,6 +62,12 @@ impl From<HashSet<String>> for ChangeAggregateValue {
     }
 }
 
+impl From<HashSet<Arc<str>>> for ChangeAggregateValue {
+    fn from(value: HashSet<Arc<str>>) -> Self {
+        as_string_string_vec(value)
+    }
+}
+
 impl From<HashSet<usize>> for ChangeAggregateValue {
     fn from(value: HashSet<usize>) -> Self {
         let mut value: Vec<_> = value.into_iter().collect();
@@ -72,6 +87,14 @@ pub enum ChangeAggregateEntity {
     Product,
     Category,
     Inventory,
+    CatalogRule,
+    Cms,
+    Customer,
+    Order,
+    Quote,
+    Config,
+    Store,
+    Eav,
 }
 
 #[derive(Debug)]
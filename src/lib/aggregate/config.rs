@@ -0,0 +1,181 @@
+use crate::aggregate::change_aggregate::{
+    ChangeAggregate, ChangeAggregateKey, ChangeAggregateValue,
+};
+use crate::aggregate::{Aggregate, ChangeAggregateEntity};
+use crate::log::{ConfigChange, ConfigValue, ItemChange};
+use crate::replication::EventMetadata;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Aggregate of configuration changes
+///
+/// Changed paths are reported under `@path` key and under scope key with scope id,
+/// values keep the first old and the last new value of the path in the batch.
+#[derive(Default)]
+pub struct ConfigAggregate {
+    paths: HashSet<Arc<str>>,
+    scoped: HashMap<(&'static str, usize), HashSet<Arc<str>>>,
+    values: HashMap<(Arc<str>, &'static str, usize), ConfigValue>,
+    size: usize,
+    last_metadata: Option<EventMetadata>,
+}
+
+impl ConfigAggregate {
+    fn process_config_change(&mut self, change: ConfigChange) {
+        let path = change.path().clone();
+        let (scope, scope_id) = change.scope();
+
+        if self.paths.insert(path.clone()) {
+            self.size += 1;
+        }
+
+        if self
+            .scoped
+            .entry((scope, scope_id))
+            .or_default()
+            .insert(path.clone())
+        {
+            self.size += 1;
+        }
+
+        if let Some((old, new)) = change.value() {
+            self.values
+                .entry((path, scope, scope_id))
+                .or_insert_with(|| (old.clone(), None))
+                .1 = new.clone();
+        }
+    }
+}
+
+impl Aggregate for ConfigAggregate {
+    fn push(&mut self, item: impl Into<ItemChange>) {
+        match item.into() {
+            ItemChange::Metadata(metadata) => self.last_metadata = Some(metadata),
+            ItemChange::ConfigChange(change) => self.process_config_change(change),
+            ItemChange::ProductChange(_) | ItemChange::EntityChange(_) => {}
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn flush(&mut self) -> Option<ChangeAggregate> {
+        let metadata = self.last_metadata.take()?;
+
+        if self.paths.is_empty() {
+            return None;
+        }
+
+        let mut change_aggregate = ChangeAggregate::new(ChangeAggregateEntity::Config, metadata);
+
+        change_aggregate.add_data(
+            ChangeAggregateKey::Key("@path"),
+            std::mem::take(&mut self.paths),
+        );
+
+        for ((scope, scope_id), paths) in std::mem::take(&mut self.scoped) {
+            change_aggregate.add_data(ChangeAggregateKey::KeyAndScopeInt(scope, scope_id), paths);
+        }
+
+        for ((path, scope, scope_id), (old, new)) in std::mem::take(&mut self.values) {
+            change_aggregate.add_data(
+                ChangeAggregateKey::PathAndScopeInt(path, scope, scope_id),
+                ChangeAggregateValue::Change(old, new),
+            );
+        }
+
+        self.size = 0;
+
+        Some(change_aggregate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::ProductChange;
+    use crate::replication::BinlogPosition;
+
+    #[test]
+    fn aggregates_paths_by_scope() {
+        let mut aggregate = ConfigAggregate::default();
+
+        aggregate.push(ConfigChange::new("web/secure/base_url", "default", 0));
+        aggregate.push(ConfigChange::new("web/secure/base_url", "websites", 1));
+        aggregate.push(ConfigChange::new("general/locale/code", "stores", 2));
+        aggregate.push(ProductChange::Created(5));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Config,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(
+                    ChangeAggregateKey::Key("@path"),
+                    ["general/locale/code", "web/secure/base_url"]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("default", 0),
+                    ["web/secure/base_url"]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("websites", 1),
+                    ["web/secure/base_url"]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("stores", 2),
+                    ["general/locale/code"]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_first_old_and_last_new_value() {
+        let mut aggregate = ConfigAggregate::default();
+
+        aggregate.push(
+            ConfigChange::new("general/locale/code", "default", 0)
+                .with_value(Some("en_US".into()), Some("nl_NL".into())),
+        );
+        aggregate.push(
+            ConfigChange::new("general/locale/code", "default", 0)
+                .with_value(Some("nl_NL".into()), Some("de_DE".into())),
+        );
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Config,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@path"), ["general/locale/code"])
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("default", 0),
+                    ["general/locale/code"]
+                )
+                .with_data(
+                    ChangeAggregateKey::PathAndScopeInt("general/locale/code".into(), "default", 0),
+                    ChangeAggregateValue::Change(Some("en_US".into()), Some("de_DE".into()))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn does_not_flush_without_changes() {
+        let mut aggregate = ConfigAggregate::default();
+
+        aggregate.push(ProductChange::Created(5));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(aggregate.flush(), None);
+    }
+}
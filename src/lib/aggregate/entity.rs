@@ -0,0 +1,257 @@
+use crate::aggregate::change_aggregate::{ChangeAggregate, ChangeAggregateKey};
+use crate::aggregate::{Aggregate, ChangeAggregateEntity};
+use crate::log::{EntityChange, ItemChange};
+use crate::replication::EventMetadata;
+use crate::schema::StoreIndex;
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Eq, PartialEq, Hash)]
+enum AggregateKey {
+    Key(&'static str),
+    Scoped(&'static str, usize),
+    Attribute(usize),
+}
+
+impl From<AggregateKey> for ChangeAggregateKey {
+    fn from(value: AggregateKey) -> Self {
+        match value {
+            AggregateKey::Key(key) => ChangeAggregateKey::Key(key),
+            AggregateKey::Scoped(key, scope) => ChangeAggregateKey::KeyAndScopeInt(key, scope),
+            AggregateKey::Attribute(id) => ChangeAggregateKey::Attribute(id),
+        }
+    }
+}
+
+/// Aggregate of changes for a single entity type other than product
+///
+/// Scoped changes are reported under the key for all scopes as well,
+/// nothing is flushed when there were no changes of the entity since last flush.
+pub struct EntityAggregate {
+    entity: ChangeAggregateEntity,
+    data: HashMap<AggregateKey, HashSet<usize>>,
+    size: usize,
+    last_metadata: Option<EventMetadata>,
+    stores: Option<StoreIndex>,
+}
+
+impl EntityAggregate {
+    pub fn new(entity: ChangeAggregateEntity) -> Self {
+        Self {
+            entity,
+            data: HashMap::new(),
+            size: 0,
+            last_metadata: None,
+            stores: None,
+        }
+    }
+
+    /// Treats scopes of the entity as stores and reports changes for all stores under every store view as well
+    pub fn with_store_index(self, stores: StoreIndex) -> Self {
+        Self {
+            stores: Some(stores),
+            ..self
+        }
+    }
+
+    fn aggregate_entity(&mut self, key: AggregateKey, entity_id: usize) {
+        if self.data.entry(key).or_default().insert(entity_id) {
+            self.size += 1;
+        }
+    }
+
+    fn process_entity_change(&mut self, change: EntityChange) {
+        if change.entity() != self.entity {
+            return;
+        }
+
+        match change {
+            EntityChange::Key(_, entity_id, key) => {
+                self.aggregate_entity(AggregateKey::Key(key), entity_id);
+            }
+            EntityChange::ScopedKey(_, entity_id, key, scope) => {
+                self.aggregate_entity(AggregateKey::Key(key), entity_id);
+
+                let scopes = match &self.stores {
+                    Some(stores) => stores.expand(scope),
+                    None => vec![scope],
+                };
+
+                for scope in scopes {
+                    self.aggregate_entity(AggregateKey::Scoped(key, scope), entity_id);
+                }
+            }
+            EntityChange::Attribute(_, entity_id, attribute_id) => {
+                self.aggregate_entity(AggregateKey::Attribute(attribute_id), entity_id);
+            }
+        }
+    }
+}
+
+impl Aggregate for EntityAggregate {
+    fn push(&mut self, item: impl Into<ItemChange>) {
+        match item.into() {
+            ItemChange::Metadata(metadata) => self.last_metadata = Some(metadata),
+            ItemChange::EntityChange(change) => self.process_entity_change(change),
+            ItemChange::ProductChange(_) | ItemChange::ConfigChange(_) => {}
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn flush(&mut self) -> Option<ChangeAggregate> {
+        let metadata = self.last_metadata.take()?;
+
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let mut change_aggregate = ChangeAggregate::new(self.entity, metadata);
+
+        for (key, value) in std::mem::take(&mut self.data) {
+            change_aggregate.add_data(key.into(), value);
+        }
+
+        self.size = 0;
+
+        Some(change_aggregate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::ProductChange;
+    use crate::replication::BinlogPosition;
+
+    #[test]
+    fn aggregates_changes_of_own_entity() {
+        let mut aggregate = EntityAggregate::new(ChangeAggregateEntity::CatalogRule);
+
+        aggregate.push(EntityChange::Key(
+            ChangeAggregateEntity::CatalogRule,
+            1,
+            "@rule",
+        ));
+        aggregate.push(EntityChange::ScopedKey(
+            ChangeAggregateEntity::CatalogRule,
+            2,
+            "@website",
+            1,
+        ));
+        aggregate.push(EntityChange::ScopedKey(
+            ChangeAggregateEntity::CatalogRule,
+            3,
+            "@website",
+            2,
+        ));
+        aggregate.push(EntityChange::Key(
+            ChangeAggregateEntity::Category,
+            4,
+            "@rule",
+        ));
+        aggregate.push(ProductChange::Created(5));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::CatalogRule,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@rule"), [1])
+                .with_data(ChangeAggregateKey::Key("@website"), [2, 3])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@website", 1), [2])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@website", 2), [3])
+            )
+        );
+    }
+
+    #[test]
+    fn aggregates_attribute_changes() {
+        let mut aggregate = EntityAggregate::new(ChangeAggregateEntity::Customer);
+
+        aggregate.push(EntityChange::Attribute(
+            ChangeAggregateEntity::Customer,
+            1,
+            5,
+        ));
+        aggregate.push(EntityChange::Attribute(
+            ChangeAggregateEntity::Customer,
+            2,
+            5,
+        ));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Customer,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Attribute(5), [1, 2])
+            )
+        );
+    }
+
+    #[test]
+    fn does_not_flush_without_changes() {
+        let mut aggregate = EntityAggregate::new(ChangeAggregateEntity::CatalogRule);
+
+        aggregate.push(ProductChange::Created(5));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(aggregate.flush(), None);
+    }
+
+    #[test]
+    fn resets_size_on_flush() {
+        let mut aggregate = EntityAggregate::new(ChangeAggregateEntity::CatalogRule);
+
+        aggregate.push(EntityChange::Key(
+            ChangeAggregateEntity::CatalogRule,
+            1,
+            "@rule",
+        ));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+        let size = aggregate.size();
+        aggregate.flush();
+
+        assert_eq!((size, aggregate.size()), (1, 0));
+    }
+
+    #[test]
+    fn expands_changes_for_all_stores_into_store_views() {
+        let stores = StoreIndex::default();
+        stores.insert(0, 0);
+        stores.insert(1, 1);
+
+        let mut aggregate =
+            EntityAggregate::new(ChangeAggregateEntity::Cms).with_store_index(stores);
+
+        aggregate.push(EntityChange::ScopedKey(
+            ChangeAggregateEntity::Cms,
+            2,
+            "@page",
+            0,
+        ));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Cms,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@page"), [2])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@page", 0), [2])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@page", 1), [2])
+            )
+        )
+    }
+}
@@ -1,5 +1,7 @@
 mod chain_aggregate;
 mod change_aggregate;
+mod config;
+mod entity;
 mod product;
 mod wrapped_aggregate;
 
@@ -8,6 +10,8 @@ use crate::error::Error;
 use crate::log::ItemChange;
 use crate::output::Output;
 pub use change_aggregate::*;
+pub use config::ConfigAggregate;
+pub use entity::EntityAggregate;
 pub use product::*;
 use std::time::Duration;
 use tokio::io::AsyncWrite;
@@ -2,9 +2,31 @@ use crate::aggregate::change_aggregate::{ChangeAggregate, ChangeAggregateKey};
 use crate::aggregate::{Aggregate, ChangeAggregateEntity};
 use crate::log::{ItemChange, ProductChange};
 use crate::replication::EventMetadata;
+use crate::schema::{AttributeCodes, StoreIndex};
 
 use std::collections::{HashMap, HashSet};
 
+/// Keys of price changes for all scopes, scoped by website and scoped by customer group
+type PriceKeys = (&'static str, &'static str, &'static str);
+
+const TIER_PRICE_KEYS: PriceKeys = (
+    "@tier_price",
+    "@tier_price_website",
+    "@tier_price_customer_group",
+);
+
+const INDEX_PRICE_KEYS: PriceKeys = (
+    "@index_price",
+    "@index_price_website",
+    "@index_price_customer_group",
+);
+
+const CATALOG_RULE_KEYS: PriceKeys = (
+    "@catalog_rule",
+    "@catalog_rule_website",
+    "@catalog_rule_customer_group",
+);
+
 
 #[derive(Eq, PartialEq, Hash)]
 enum AggregateKey {
@@ -12,20 +34,33 @@ enum AggregateKey {
     Deleted,
     Field(&'static str),
     Attribute(usize),
+    AttributeStore(usize, usize),
     WebsiteAll,
     WebsiteSpecific(usize),
     CategoryAll,
     CategorySpecific(usize),
+    CategoryPositionAll,
+    CategoryPositionSpecific(usize),
     Link(usize),
     Composite,
-    MediaGallery,
-    TierPrice,
+    MediaGalleryAll,
+    MediaGallerySpecific(usize),
+    Custom(&'static str),
+    CustomScoped(&'static str, usize),
+    StagingAll,
+    StagingSpecific(usize),
+    SuperAttribute,
+    Options,
+    ChildChanged,
 }
 
 impl Into<ChangeAggregateKey> for AggregateKey {
     fn into(self) -> ChangeAggregateKey {
         match self {
             Self::Attribute(id) => ChangeAggregateKey::Attribute(id),
+            Self::AttributeStore(id, store_id) => {
+                ChangeAggregateKey::AttributeAndScopeInt(id, store_id)
+            }
             Self::Created => ChangeAggregateKey::Key("@created"),
             Self::Deleted => ChangeAggregateKey::Key("@deleted"),
             Self::Field(field) => ChangeAggregateKey::Key(field),
@@ -33,10 +68,23 @@ impl Into<ChangeAggregateKey> for AggregateKey {
             Self::WebsiteSpecific(id) => ChangeAggregateKey::KeyAndScopeInt("@website", id),
             Self::CategoryAll => ChangeAggregateKey::Key("@category"),
             Self::CategorySpecific(id) => ChangeAggregateKey::KeyAndScopeInt("@category", id),
+            Self::CategoryPositionAll => ChangeAggregateKey::Key("@category_position"),
+            Self::CategoryPositionSpecific(id) => {
+                ChangeAggregateKey::KeyAndScopeInt("@category_position", id)
+            }
             Self::Link(id) => ChangeAggregateKey::KeyAndScopeInt("@link", id),
             Self::Composite => ChangeAggregateKey::Key("@composite"),
-            Self::MediaGallery => ChangeAggregateKey::Key("@media_gallery"),
-            Self::TierPrice => ChangeAggregateKey::Key("@tier_price"),
+            Self::MediaGalleryAll => ChangeAggregateKey::Key("@media_gallery"),
+            Self::MediaGallerySpecific(id) => {
+                ChangeAggregateKey::KeyAndScopeInt("@media_gallery", id)
+            }
+            Self::Custom(key) => ChangeAggregateKey::Key(key),
+            Self::CustomScoped(key, id) => ChangeAggregateKey::KeyAndScopeInt(key, id),
+            Self::StagingAll => ChangeAggregateKey::Key("@staging"),
+            Self::StagingSpecific(id) => ChangeAggregateKey::KeyAndScopeInt("@staging", id),
+            Self::SuperAttribute => ChangeAggregateKey::Key("@super_attribute"),
+            Self::Options => ChangeAggregateKey::Key("@options"),
+            Self::ChildChanged => ChangeAggregateKey::Key("@child_changed"),
         }
     }
 }
@@ -46,9 +94,46 @@ pub struct ProductAggregate {
     data: HashMap<AggregateKey, HashSet<usize>>,
     size: usize,
     last_metadata: Option<EventMetadata>,
+    attribute_codes: Option<AttributeCodes>,
+    stores: Option<StoreIndex>,
 }
 
 impl ProductAggregate {
+    /// Reports attribute changes by attribute code instead of id, when code is known
+    pub fn with_attribute_codes(self, attribute_codes: AttributeCodes) -> Self {
+        Self {
+            attribute_codes: Some(attribute_codes),
+            ..self
+        }
+    }
+
+    /// Reports attribute and media gallery changes for all stores under every store view as well
+    pub fn with_store_index(self, stores: StoreIndex) -> Self {
+        Self {
+            stores: Some(stores),
+            ..self
+        }
+    }
+
+    fn resolve_key(&self, key: AggregateKey) -> ChangeAggregateKey {
+        let codes = match &self.attribute_codes {
+            Some(codes) => codes,
+            None => return key.into(),
+        };
+
+        match key {
+            AggregateKey::Attribute(id) => match codes.code(id) {
+                Some(code) => ChangeAggregateKey::AttributeCode(code, id),
+                None => key.into(),
+            },
+            AggregateKey::AttributeStore(id, store_id) => match codes.code(id) {
+                Some(code) => ChangeAggregateKey::AttributeCodeAndScopeInt(code, id, store_id),
+                None => key.into(),
+            },
+            key => key.into(),
+        }
+    }
+
     fn aggregate_product(&mut self, key: AggregateKey, entity_id: usize) {
         let is_inserted = self.data.entry(key).or_default().insert(entity_id);
 
@@ -57,10 +142,45 @@ impl ProductAggregate {
         }
     }
 
+    fn store_scopes(&self, store_id: usize) -> Vec<usize> {
+        match &self.stores {
+            Some(stores) => stores.expand(store_id),
+            None => vec![store_id],
+        }
+    }
+
+    fn aggregate_price(
+        &mut self,
+        (key, website_key, customer_group_key): PriceKeys,
+        entity_id: usize,
+        website_id: usize,
+        customer_group_id: Option<usize>,
+    ) {
+        self.aggregate_product(AggregateKey::Custom(key), entity_id);
+        self.aggregate_product(
+            AggregateKey::CustomScoped(website_key, website_id),
+            entity_id,
+        );
+
+        if let Some(customer_group_id) = customer_group_id {
+            self.aggregate_product(
+                AggregateKey::CustomScoped(customer_group_key, customer_group_id),
+                entity_id,
+            );
+        }
+    }
+
     fn process_product_change(&mut self, change: ProductChange) {
         match change {
-            ProductChange::Attribute(entity_id, attribute_id) => {
+            ProductChange::Attribute(entity_id, attribute_id, store_id) => {
                 self.aggregate_product(AggregateKey::Attribute(attribute_id), entity_id);
+
+                for store_id in self.store_scopes(store_id) {
+                    self.aggregate_product(
+                        AggregateKey::AttributeStore(attribute_id, store_id),
+                        entity_id,
+                    );
+                }
             }
             ProductChange::Fields(entity_id, fields) => {
                 for field in fields {
@@ -82,17 +202,64 @@ impl ProductAggregate {
                 self.aggregate_product(AggregateKey::CategoryAll, entity_id);
                 self.aggregate_product(AggregateKey::CategorySpecific(category_id), entity_id);
             }
+            ProductChange::CategoryPosition(entity_id, category_id) => {
+                self.aggregate_product(AggregateKey::CategoryPositionAll, entity_id);
+                self.aggregate_product(
+                    AggregateKey::CategoryPositionSpecific(category_id),
+                    entity_id,
+                );
+            }
             ProductChange::LinkRelation(entity_id, type_id) => {
                 self.aggregate_product(AggregateKey::Link(type_id), entity_id);
             }
-            ProductChange::MediaGallery(entity_id) => {
-                self.aggregate_product(AggregateKey::MediaGallery, entity_id);
+            ProductChange::MediaGallery(entity_id, store_id) => {
+                self.aggregate_product(AggregateKey::MediaGalleryAll, entity_id);
+
+                for store_id in self.store_scopes(store_id) {
+                    self.aggregate_product(AggregateKey::MediaGallerySpecific(store_id), entity_id);
+                }
             }
             ProductChange::CompositeRelation(entity_id) => {
                 self.aggregate_product(AggregateKey::Composite, entity_id);
             }
-            ProductChange::TierPrice(entity_id) => {
-                self.aggregate_product(AggregateKey::TierPrice, entity_id);
+            ProductChange::TierPrice(entity_id, website_id, customer_group_id) => {
+                self.aggregate_price(TIER_PRICE_KEYS, entity_id, website_id, customer_group_id);
+            }
+            ProductChange::IndexPrice(entity_id, website_id, customer_group_id) => {
+                self.aggregate_price(
+                    INDEX_PRICE_KEYS,
+                    entity_id,
+                    website_id,
+                    Some(customer_group_id),
+                );
+            }
+            ProductChange::CatalogRule(entity_id, website_id, customer_group_id) => {
+                self.aggregate_price(
+                    CATALOG_RULE_KEYS,
+                    entity_id,
+                    website_id,
+                    Some(customer_group_id),
+                );
+            }
+            ProductChange::Key(entity_id, key) => {
+                self.aggregate_product(AggregateKey::Custom(key), entity_id);
+            }
+            ProductChange::ScopedKey(entity_id, key, scope) => {
+                self.aggregate_product(AggregateKey::Custom(key), entity_id);
+                self.aggregate_product(AggregateKey::CustomScoped(key, scope), entity_id);
+            }
+            ProductChange::StagingUpdate(entity_id, version) => {
+                self.aggregate_product(AggregateKey::StagingAll, entity_id);
+                self.aggregate_product(AggregateKey::StagingSpecific(version), entity_id);
+            }
+            ProductChange::SuperAttribute(entity_id) => {
+                self.aggregate_product(AggregateKey::SuperAttribute, entity_id);
+            }
+            ProductChange::Options(entity_id) => {
+                self.aggregate_product(AggregateKey::Options, entity_id);
+            }
+            ProductChange::ChildChanged(entity_id) => {
+                self.aggregate_product(AggregateKey::ChildChanged, entity_id);
             }
             _ => {}
         }
@@ -106,6 +273,7 @@ impl Aggregate for ProductAggregate {
             ItemChange::ProductChange(product_change) => {
                 self.process_product_change(product_change)
             }
+            ItemChange::EntityChange(_) | ItemChange::ConfigChange(_) => {}
         }
     }
 
@@ -123,7 +291,7 @@ impl Aggregate for ProductAggregate {
         let data = std::mem::take(&mut self.data);
 
         for (key, value) in data.into_iter() {
-            change_aggregate.add_data(key.into(), value);
+            change_aggregate.add_data(self.resolve_key(key), value);
         }
 
         Some(change_aggregate)
@@ -136,30 +304,30 @@ mod tests {
     use crate::aggregate::change_aggregate::ChangeAggregateKey;
     use crate::aggregate::ChangeAggregateEntity;
     use crate::log::ProductChange;
-    use crate::replication::BinlogPosition;
+    use crate::replication::{BinlogPosition, Event};
 
     #[test]
     fn returns_size_in_bytes_for_data_container() {
         let mut aggregate = ProductAggregate::default();
 
-        aggregate.push(ProductChange::Attribute(1, 1));
-        aggregate.push(ProductChange::Attribute(2, 1));
-        aggregate.push(ProductChange::Attribute(3, 1));
-        aggregate.push(ProductChange::Attribute(3, 2));
-        aggregate.push(ProductChange::Attribute(4, 2));
-        aggregate.push(ProductChange::Attribute(4, 2));
+        aggregate.push(ProductChange::Attribute(1, 1, 0));
+        aggregate.push(ProductChange::Attribute(2, 1, 0));
+        aggregate.push(ProductChange::Attribute(3, 1, 0));
+        aggregate.push(ProductChange::Attribute(3, 2, 0));
+        aggregate.push(ProductChange::Attribute(4, 2, 0));
+        aggregate.push(ProductChange::Attribute(4, 2, 0));
 
-        assert_eq!(aggregate.size(), 5);
+        assert_eq!(aggregate.size(), 10);
     }
 
     #[test]
     fn aggregates_attribute_changes() {
         let mut aggregate = ProductAggregate::default();
 
-        aggregate.push(ProductChange::Attribute(2, 1));
-        aggregate.push(ProductChange::Attribute(2, 1));
-        aggregate.push(ProductChange::Attribute(3, 1));
-        aggregate.push(ProductChange::Attribute(1, 2));
+        aggregate.push(ProductChange::Attribute(2, 1, 0));
+        aggregate.push(ProductChange::Attribute(2, 1, 1));
+        aggregate.push(ProductChange::Attribute(3, 1, 1));
+        aggregate.push(ProductChange::Attribute(1, 2, 0));
         aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
 
         assert_eq!(
@@ -170,7 +338,47 @@ mod tests {
                     EventMetadata::new(1, BinlogPosition::new("file", 1))
                 )
                 .with_data(ChangeAggregateKey::Attribute(1), [2, 3])
+                .with_data(ChangeAggregateKey::AttributeAndScopeInt(1, 0), [2])
+                .with_data(ChangeAggregateKey::AttributeAndScopeInt(1, 1), [2, 3])
                 .with_data(ChangeAggregateKey::Attribute(2), [1])
+                .with_data(ChangeAggregateKey::AttributeAndScopeInt(2, 0), [1])
+            )
+        )
+    }
+
+    #[test]
+    fn aggregates_attribute_changes_by_code() {
+        let codes = AttributeCodes::default();
+        codes
+            .apply_event(
+                &Event::InsertRow(binlog_row!(1, 4, "name")),
+                &test_table!(
+                    "eav_attribute",
+                    ["attribute_id", "entity_type_id", "attribute_code"]
+                ),
+            )
+            .unwrap();
+
+        let mut aggregate = ProductAggregate::default().with_attribute_codes(codes);
+
+        aggregate.push(ProductChange::Attribute(2, 1, 1));
+        aggregate.push(ProductChange::Attribute(3, 2, 0));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::AttributeCode("name".into(), 1), [2])
+                .with_data(
+                    ChangeAggregateKey::AttributeCodeAndScopeInt("name".into(), 1, 1),
+                    [2]
+                )
+                .with_data(ChangeAggregateKey::Attribute(2), [3])
+                .with_data(ChangeAggregateKey::AttributeAndScopeInt(2, 0), [3])
             )
         )
     }
@@ -308,8 +516,8 @@ mod tests {
 
         aggregate.push(ProductChange::CompositeRelation(1));
         aggregate.push(ProductChange::CompositeRelation(2));
-        aggregate.push(ProductChange::MediaGallery(2));
-        aggregate.push(ProductChange::MediaGallery(3));
+        aggregate.push(ProductChange::MediaGallery(2, 0));
+        aggregate.push(ProductChange::MediaGallery(3, 1));
         aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
 
         assert_eq!(
@@ -321,6 +529,8 @@ mod tests {
                 )
                 .with_data(ChangeAggregateKey::Key("@composite"), [1, 2])
                 .with_data(ChangeAggregateKey::Key("@media_gallery"), [2, 3])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@media_gallery", 0), [2])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@media_gallery", 1), [3])
             )
         )
     }
@@ -329,8 +539,8 @@ mod tests {
     fn aggregates_tier_price_changes() {
         let mut aggregate = ProductAggregate::default();
 
-        aggregate.push(ProductChange::TierPrice(1));
-        aggregate.push(ProductChange::TierPrice(2));
+        aggregate.push(ProductChange::TierPrice(1, 0, None));
+        aggregate.push(ProductChange::TierPrice(2, 1, Some(3)));
         aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
 
         assert_eq!(
@@ -341,6 +551,219 @@ mod tests {
                     EventMetadata::new(1, BinlogPosition::new("file", 1))
                 )
                 .with_data(ChangeAggregateKey::Key("@tier_price"), [1, 2])
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@tier_price_website", 0),
+                    [1]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@tier_price_website", 1),
+                    [2]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@tier_price_customer_group", 3),
+                    [2]
+                )
+            )
+        )
+    }
+
+    #[test]
+    fn aggregates_index_price_and_catalog_rule_changes() {
+        let mut aggregate = ProductAggregate::default();
+
+        aggregate.push(ProductChange::IndexPrice(1, 1, 0));
+        aggregate.push(ProductChange::CatalogRule(2, 1, 1));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@index_price"), [1])
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@index_price_website", 1),
+                    [1]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@index_price_customer_group", 0),
+                    [1]
+                )
+                .with_data(ChangeAggregateKey::Key("@catalog_rule"), [2])
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@catalog_rule_website", 1),
+                    [2]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@catalog_rule_customer_group", 1),
+                    [2]
+                )
+            )
+        )
+    }
+
+    #[test]
+    fn aggregates_super_attribute_and_option_changes() {
+        let mut aggregate = ProductAggregate::default();
+
+        aggregate.push(ProductChange::SuperAttribute(1));
+        aggregate.push(ProductChange::Options(2));
+        aggregate.push(ProductChange::Options(3));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@super_attribute"), [1])
+                .with_data(ChangeAggregateKey::Key("@options"), [2, 3])
+            )
+        )
+    }
+
+    #[test]
+    fn expands_attribute_and_media_gallery_changes_for_all_stores_into_store_views() {
+        let stores = StoreIndex::default();
+        stores.insert(0, 0);
+        stores.insert(1, 1);
+        stores.insert(2, 1);
+
+        let mut aggregate = ProductAggregate::default().with_store_index(stores);
+
+        aggregate.push(ProductChange::Attribute(1, 5, 0));
+        aggregate.push(ProductChange::Attribute(2, 5, 2));
+        aggregate.push(ProductChange::MediaGallery(3, 0));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Attribute(5), [1, 2])
+                .with_data(ChangeAggregateKey::AttributeAndScopeInt(5, 0), [1])
+                .with_data(ChangeAggregateKey::AttributeAndScopeInt(5, 1), [1])
+                .with_data(ChangeAggregateKey::AttributeAndScopeInt(5, 2), [1, 2])
+                .with_data(ChangeAggregateKey::Key("@media_gallery"), [3])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@media_gallery", 0), [3])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@media_gallery", 1), [3])
+                .with_data(ChangeAggregateKey::KeyAndScopeInt("@media_gallery", 2), [3])
+            )
+        )
+    }
+
+    #[test]
+    fn aggregates_category_position_changes_scoped_by_category() {
+        let mut aggregate = ProductAggregate::default();
+
+        aggregate.push(ProductChange::CategoryPosition(1, 3));
+        aggregate.push(ProductChange::CategoryPosition(2, 3));
+        aggregate.push(ProductChange::CategoryPosition(2, 4));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@category_position"), [1, 2])
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@category_position", 3),
+                    [1, 2]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@category_position", 4),
+                    [2]
+                )
+            )
+        )
+    }
+
+    #[test]
+    fn aggregates_child_changes_of_parents() {
+        let mut aggregate = ProductAggregate::default();
+
+        aggregate.push(ProductChange::ChildChanged(10));
+        aggregate.push(ProductChange::ChildChanged(20));
+        aggregate.push(ProductChange::ChildChanged(10));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@child_changed"), [10, 20])
+            )
+        )
+    }
+
+    #[test]
+    fn aggregates_configured_key_changes() {
+        let mut aggregate = ProductAggregate::default();
+
+        aggregate.push(ProductChange::Key(1, "custom_flag"));
+        aggregate.push(ProductChange::ScopedKey(2, "custom_store", 1));
+        aggregate.push(ProductChange::ScopedKey(3, "custom_store", 2));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("custom_flag"), [1])
+                .with_data(ChangeAggregateKey::Key("custom_store"), [2, 3])
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("custom_store", 1),
+                    [2]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("custom_store", 2),
+                    [3]
+                )
+            )
+        )
+    }
+
+    #[test]
+    fn aggregates_staging_updates() {
+        let mut aggregate = ProductAggregate::default();
+
+        aggregate.push(ProductChange::StagingUpdate(1, 1700000000));
+        aggregate.push(ProductChange::StagingUpdate(2, 1700000000));
+        aggregate.push(ProductChange::StagingUpdate(2, 1800000000));
+        aggregate.push(EventMetadata::new(1, BinlogPosition::new("file", 1)));
+
+        assert_eq!(
+            aggregate.flush(),
+            Some(
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(1, BinlogPosition::new("file", 1))
+                )
+                .with_data(ChangeAggregateKey::Key("@staging"), [1, 2])
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@staging", 1700000000),
+                    [1, 2]
+                )
+                .with_data(
+                    ChangeAggregateKey::KeyAndScopeInt("@staging", 1800000000),
+                    [2]
+                )
             )
         )
     }
@@ -1,16 +1,34 @@
-use crate::aggregate::{AsyncAggregate, ProductAggregate, WrappedAggregate};
+use crate::aggregate::{
+    AsyncAggregate, ChangeAggregateEntity, ConfigAggregate, EntityAggregate, ProductAggregate,
+    WrappedAggregate,
+};
 use crate::app::{ApplicationConfig, ApplicationOutput};
 use crate::database::Database;
 use crate::error::Error;
 use crate::log::{ChangeLogSender, ItemChange};
-use crate::mapper::{ChainMapper, ChangeLogMapper, MagentoTwoMapper, MapperObserver};
-use crate::replication::{BinlogPosition, ReplicationClient};
+use crate::mapper::{
+    ChainMapper, ChangeLogMapper, ConfigDataMapper, ContentStagingMapper, CustomerMapper,
+    MapperObserver, ParentPropagationMapper, Platform, PlatformMapper,
+    ProductMediaGalleryEntityMapper, ProductRelationMapper, ProductReviewMapper,
+    TableMappingMapper,
+};
+use crate::metrics::{serve_metrics, Metrics, MetricsOutput, MetricsSender};
+use crate::replication::{BinlogPosition, EventObserver, ReplicationClient};
+use crate::schema::{
+    AttributeCodes, MediaGalleryIndex, ParentIndex, RelationIndex, RowIdResolver, StoreIndex,
+};
 
 use mysql_common::packets::BinlogDumpFlags;
 use serde_json::json;
-use tokio::io::stdout;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{stdout, Stdout};
+use tokio::net::TcpListener;
 use tokio::sync::mpsc::channel;
+use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tracing::{info, warn};
 
 pub enum ApplicationCommand {
     Position(ApplicationConfig),
@@ -18,48 +36,250 @@ pub enum ApplicationCommand {
     Watch(ApplicationConfig, ApplicationOutput, BinlogPosition),
 }
 
-pub struct Application<M = MagentoTwoMapper>
+impl ApplicationCommand {
+    pub fn config(&self) -> &ApplicationConfig {
+        match self {
+            Self::Position(config) | Self::Dump(config, _, _) | Self::Watch(config, _, _) => config,
+        }
+    }
+}
+
+/// Application with built-in mapper of each database platform followed by custom mappers
+///
+/// Custom mappers are shared by all tenant databases. The ones added with `with_mapper`
+/// receive events not mapped by platform mapper, while the ones added with
+/// `with_additional_mapper` receive every event.
+pub struct Application<M = (), A = ()>
 where
     M: ChangeLogMapper<ItemChange>,
+    A: ChangeLogMapper<ItemChange>,
 {
     mapper: M,
+    additional: A,
 }
 
+/// Standard output shared by writers of all tenants, so batches are not interleaved
+type SharedStdout = Arc<Mutex<Stdout>>;
+
 async fn create_writer(
     output: ApplicationOutput,
     config: ApplicationConfig,
+    metrics: Metrics,
+    attribute_codes: Option<AttributeCodes>,
+    stores: Option<StoreIndex>,
+    stdout: SharedStdout,
 ) -> (
     impl ChangeLogSender<Item = ItemChange>,
     JoinHandle<Result<(), Error>>,
 ) {
     let (sender, mut receiver) = channel(10000);
+    let sender = MetricsSender::new(sender, metrics.clone());
 
     let handle = tokio::spawn(async move {
-        let mut aggregate = WrappedAggregate::new(ProductAggregate::default());
+        let product = match attribute_codes {
+            Some(attribute_codes) => {
+                ProductAggregate::default().with_attribute_codes(attribute_codes)
+            }
+            None => ProductAggregate::default(),
+        };
+        let (product, cms) = match stores {
+            Some(stores) => (
+                product.with_store_index(stores.clone()),
+                EntityAggregate::new(ChangeAggregateEntity::Cms).with_store_index(stores),
+            ),
+            None => (product, EntityAggregate::new(ChangeAggregateEntity::Cms)),
+        };
+
+        let mut aggregate = WrappedAggregate::new(product)
+            .with(WrappedAggregate::new(EntityAggregate::new(
+                ChangeAggregateEntity::CatalogRule,
+            )))
+            .with(WrappedAggregate::new(cms))
+            .with(WrappedAggregate::new(EntityAggregate::new(
+                ChangeAggregateEntity::Customer,
+            )))
+            .with(WrappedAggregate::new(EntityAggregate::new(
+                ChangeAggregateEntity::Order,
+            )))
+            .with(WrappedAggregate::new(EntityAggregate::new(
+                ChangeAggregateEntity::Quote,
+            )))
+            .with(WrappedAggregate::new(ConfigAggregate::default()))
+            .with(WrappedAggregate::new(EntityAggregate::new(
+                ChangeAggregateEntity::Store,
+            )))
+            .with(WrappedAggregate::new(EntityAggregate::new(
+                ChangeAggregateEntity::Eav,
+            )));
+        let output = MetricsOutput::new(output, metrics.clone());
 
         let limit = config.batch_limit();
 
         while let Some(item) = receiver.recv().await {
+            metrics.decrement_channel_depth();
             aggregate.push(item);
-            aggregate.write(&limit, &output, stdout()).await?;
+            aggregate
+                .write(&limit, &output, &mut *stdout.lock().await)
+                .await?;
         }
 
-        aggregate.write_eof(&output, stdout()).await
+        aggregate
+            .write_eof(&output, &mut *stdout.lock().await)
+            .await
     });
 
     (sender, handle)
 }
 
-impl<M> Application<M>
+/// Creates replication client, observer and writer of aggregates for database of a tenant
+///
+/// Platform mapper is built for the platform of the tenant and chained with shared custom mappers.
+async fn create_tenant<M, A>(
+    database: Database,
+    config: &ApplicationConfig,
+    mapper: Arc<M>,
+    additional: Arc<A>,
+    output: ApplicationOutput,
+    metrics: Metrics,
+    stdout: SharedStdout,
+) -> (
+    ReplicationClient<&str, &str>,
+    impl EventObserver,
+    JoinHandle<Result<(), Error>>,
+)
 where
     M: ChangeLogMapper<ItemChange>,
+    A: ChangeLogMapper<ItemChange>,
 {
-    pub fn with_mapper<R>(self, mapper: R) -> Application<ChainMapper<M, R>>
+    let attribute_codes = config.attribute_codes().then(AttributeCodes::default);
+    let stores = config.expand_all_stores().then(StoreIndex::default);
+    let (sender, handle) = create_writer(
+        output,
+        config.clone(),
+        metrics.clone(),
+        attribute_codes.clone(),
+        stores.clone(),
+        stdout,
+    )
+    .await;
+
+    let client = ReplicationClient::new(database, config.database(), config.table_prefix())
+        .with_statement(config.include_statement())
+        .with_metrics(metrics)
+        .with_tenant(config.tenant());
+
+    // Content staging and gallery image links exist only in Magento 2 databases,
+    // while custom options, reviews and customer addresses are the same in OpenMage
+    let magento_two = config.platform() == Platform::MagentoTwo;
+    let row_ids = RowIdResolver::default();
+    let relations = RelationIndex::default();
+    let media_gallery =
+        magento_two.then(|| MediaGalleryIndex::default().with_row_id_resolver(row_ids.clone()));
+    let client = client.with_relation_index(relations.clone());
+
+    let client = match magento_two {
+        true => client.with_row_id_resolver(row_ids.clone()),
+        false => client,
+    };
+
+    let client = match &media_gallery {
+        Some(media_gallery) => client.with_media_gallery_index(media_gallery.clone()),
+        None => client,
+    };
+
+    let client = match attribute_codes {
+        Some(attribute_codes) => client.with_attribute_codes(attribute_codes),
+        None => client,
+    };
+
+    let client = match stores {
+        Some(stores) => client.with_store_index(stores),
+        None => client,
+    };
+
+    let parents = config
+        .propagate_parents()
+        .then(|| ParentIndex::default().with_row_id_resolver(row_ids.clone()));
+    let client = match &parents {
+        Some(parents) => client.with_parent_index(parents.clone()),
+        None => client,
+    };
+
+    let mapper = ChainMapper::all(
+        ChainMapper::new(PlatformMapper::from(config.platform()), mapper),
+        additional,
+    );
+
+    let mapper = ParentPropagationMapper::new(
+        ChainMapper::new(
+            TableMappingMapper::from(config.mappings()),
+            ChainMapper::new(
+                ContentStagingMapper::new(
+                    ChainMapper::new(mapper, ProductRelationMapper::new(relations.clone())),
+                    row_ids,
+                ),
+                ChainMapper::new(
+                    media_gallery.map(ProductMediaGalleryEntityMapper::new),
+                    ChainMapper::new(
+                        ProductReviewMapper::new(relations.clone()),
+                        ChainMapper::new(
+                            CustomerMapper::new(relations),
+                            ConfigDataMapper::from(config.config_data()),
+                        ),
+                    ),
+                ),
+            ),
+        ),
+        parents,
+    );
+
+    (client, MapperObserver::from((mapper, sender)), handle)
+}
+
+async fn monitor_lag(mut database: Database, metrics: Metrics, period: Duration) {
+    let mut interval = interval(period);
+    let lag = metrics.lag();
+
+    loop {
+        interval.tick().await;
+
+        match database.binlog_position().await {
+            Ok(position) => lag.observe_master_position(position),
+            Err(error) => warn!("Cannot read source server binlog position: {error}"),
+        }
+
+        info!(
+            seconds_behind = lag.seconds_behind(),
+            bytes_behind = lag.bytes_behind(),
+            files_behind = lag.files_behind(),
+            "Replication lag"
+        );
+    }
+}
+
+impl<M, A> Application<M, A>
+where
+    M: ChangeLogMapper<ItemChange>,
+    A: ChangeLogMapper<ItemChange>,
+{
+    pub fn with_mapper<R>(self, mapper: R) -> Application<ChainMapper<M, R>, A>
     where
         R: ChangeLogMapper<ItemChange>,
     {
         Application {
             mapper: ChainMapper::new(self.mapper, mapper),
+            additional: self.additional,
+        }
+    }
+
+    /// Adds mapper that receives every event, including tables already mapped by other mappers
+    pub fn with_additional_mapper<R>(self, mapper: R) -> Application<M, ChainMapper<A, R>>
+    where
+        R: ChangeLogMapper<ItemChange>,
+    {
+        Application {
+            mapper: self.mapper,
+            additional: ChainMapper::all(self.additional, mapper),
         }
     }
 
@@ -70,15 +290,77 @@ where
         output: ApplicationOutput,
         position: BinlogPosition,
     ) -> Result<(), Error> {
-        let (sender, handle) = create_writer(output, config.clone()).await;
+        let metrics = Metrics::default();
+        let stdout = Arc::new(Mutex::new(stdout()));
+        let mapper = Arc::new(self.mapper);
+        let additional = Arc::new(self.additional);
+        let tenant_configs = config.tenant_configs()?;
+
+        let (client, observer, handle) = create_tenant(
+            database.clone(),
+            &config,
+            mapper.clone(),
+            additional.clone(),
+            output,
+            metrics.clone(),
+            stdout.clone(),
+        )
+        .await;
+
+        let mut tenants = Vec::new();
+        let mut handles = vec![handle];
 
-        let client = ReplicationClient::new(database, config.database(), config.table_prefix());
+        for tenant_config in &tenant_configs {
+            let (client, observer, handle) = create_tenant(
+                database.clone(),
+                tenant_config,
+                mapper.clone(),
+                additional.clone(),
+                output,
+                metrics.clone(),
+                stdout.clone(),
+            )
+            .await;
 
-        client
-            .process(MapperObserver::from((self.mapper, sender)), position)
-            .await?;
+            tenants.push((client, observer));
+            handles.push(handle);
+        }
+
+        let server = match config.metrics_listen() {
+            Some(address) => Some(tokio::spawn(serve_metrics(
+                TcpListener::bind(address).await?,
+                metrics.clone(),
+                config.heartbeat_period() * 3,
+            ))),
+            None => None,
+        };
+
+        let monitor = tokio::spawn(monitor_lag(
+            database.clone(),
+            metrics.clone(),
+            config.heartbeat_period(),
+        ));
+
+        let client = match config.reconnect_delay() {
+            Some(delay) => client.with_reconnect(delay),
+            None => client,
+        };
+
+        let result = client.process_tenants(observer, tenants, position).await;
 
-        handle.await.map_err(|_| Error::Synchronization)?
+        monitor.abort();
+
+        if let Some(server) = server {
+            server.abort();
+        }
+
+        result?;
+
+        for handle in handles {
+            handle.await.map_err(|_| Error::Synchronization)??;
+        }
+
+        Ok(())
     }
 
     pub async fn run(self, command: ApplicationCommand) -> Result<(), Error> {
@@ -122,7 +404,8 @@ where
 impl Application {
     pub fn new() -> Self {
         Self {
-            mapper: MagentoTwoMapper,
+            mapper: (),
+            additional: (),
         }
     }
 }
@@ -1,17 +1,107 @@
 use crate::error::Error;
-use crate::replication::BinlogPosition;
+use crate::replication::{
+    BinaryRow, BinlogPosition, BinlogRowImage, BINLOG_SEND_ANNOTATE_ROWS_EVENT,
+    MARIADB_SLAVE_CAPABILITY,
+};
 use std::cmp::max;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 use mysql_async::prelude::Queryable;
 use mysql_async::{BinlogStream, Conn, Opts, Pool};
+use mysql_common::binlog::value::BinlogValue;
 use mysql_common::packets::binlog_request::BinlogRequest;
 use mysql_common::packets::BinlogDumpFlags;
 use mysql_common::row::Row;
+use mysql_common::value::Value;
 
 #[derive(Clone, Debug)]
 pub struct Database {
     pool: Pool,
     dump_options: BinlogDumpFlags,
+    annotate_rows: bool,
+    heartbeat_period: Option<Duration>,
+    server_id: Option<u32>,
+    version: Arc<OnceLock<ServerVersion>>,
+    /// Server id of the last opened binlog stream, zero before the first one
+    stream_server_id: Arc<AtomicU32>,
+}
+
+/// Version of the database server used to pick compatible replication statements
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ServerVersion {
+    major: u16,
+    minor: u16,
+    patch: u16,
+    mariadb: bool,
+}
+
+impl ServerVersion {
+    pub fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            mariadb: false,
+        }
+    }
+
+    pub fn mariadb(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            mariadb: true,
+            ..Self::new(major, minor, patch)
+        }
+    }
+
+    /// Parses value of `SELECT VERSION()` like `8.4.0` or `10.11.2-MariaDB-1:10.11.2+maria~ubu2204`
+    pub fn parse(version: impl AsRef<str>) -> Self {
+        let version = version.as_ref();
+        let mut parts = version
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|part| part.parse().unwrap_or_default());
+
+        Self {
+            major: parts.next().unwrap_or_default(),
+            minor: parts.next().unwrap_or_default(),
+            patch: parts.next().unwrap_or_default(),
+            mariadb: version.contains("MariaDB"),
+        }
+    }
+
+    pub fn is_mariadb(&self) -> bool {
+        self.mariadb
+    }
+
+    fn is_at_least(&self, major: u16, minor: u16, patch: u16) -> bool {
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+
+    /// Statement that lists replicas connected to the server
+    pub fn replicas_statement(&self) -> &'static str {
+        match (self.mariadb, self) {
+            (true, version) if version.is_at_least(10, 5, 1) => "SHOW REPLICA HOSTS",
+            (false, version) if version.is_at_least(8, 0, 22) => "SHOW REPLICAS",
+            _ => "SHOW SLAVE HOSTS",
+        }
+    }
+
+    /// Statement that returns current binary log file and position
+    pub fn binary_log_status_statement(&self) -> &'static str {
+        match (self.mariadb, self) {
+            (true, version) if version.is_at_least(10, 5, 2) => "SHOW BINLOG STATUS",
+            (false, version) if version.is_at_least(8, 2, 0) => "SHOW BINARY LOG STATUS",
+            _ => "SHOW MASTER STATUS",
+        }
+    }
+
+    /// Binlog dump flags with annotate rows flag added only for MariaDB, as MySQL has no such events
+    pub fn dump_flags(&self, flags: BinlogDumpFlags, annotate_rows: bool) -> BinlogDumpFlags {
+        match annotate_rows && self.is_mariadb() {
+            true => flags | BINLOG_SEND_ANNOTATE_ROWS_EVENT,
+            false => flags,
+        }
+    }
 }
 
 impl Database {
@@ -22,6 +112,11 @@ impl Database {
         Self {
             pool: Pool::new(opts),
             dump_options: BinlogDumpFlags::BINLOG_DUMP_NON_BLOCK,
+            annotate_rows: false,
+            heartbeat_period: None,
+            server_id: None,
+            version: Arc::default(),
+            stream_server_id: Arc::default(),
         }
     }
 
@@ -29,6 +124,11 @@ impl Database {
         Self {
             pool,
             dump_options: BinlogDumpFlags::BINLOG_DUMP_NON_BLOCK,
+            annotate_rows: false,
+            heartbeat_period: None,
+            server_id: None,
+            version: Arc::default(),
+            stream_server_id: Arc::default(),
         }
     }
 
@@ -39,40 +139,111 @@ impl Database {
         }
     }
 
+    /// Requests MariaDB server to send SQL statement of rows events in `ANNOTATE_ROWS_EVENT`
+    pub fn with_annotate_rows(self, annotate_rows: bool) -> Self {
+        Self {
+            annotate_rows,
+            ..self
+        }
+    }
+
+    /// Requests source server to send heartbeat events when there is no new events in binlog
+    pub fn with_heartbeat_period(self, period: Duration) -> Self {
+        Self {
+            heartbeat_period: Some(period),
+            ..self
+        }
+    }
+
+    /// Uses explicit server id for binlog stream instead of the next one after connected replicas
+    pub fn with_server_id(self, server_id: u32) -> Self {
+        Self {
+            server_id: Some(server_id),
+            ..self
+        }
+    }
+
+    /// Version of the server, queried once and shared by all clones
+    pub async fn server_version(&self) -> Result<ServerVersion, Error> {
+        if let Some(version) = self.version.get() {
+            return Ok(*version);
+        }
+
+        let version: Option<String> = self
+            .acquire_connection()
+            .await?
+            .query_first("SELECT VERSION()")
+            .await?;
+
+        Ok(*self
+            .version
+            .get_or_init(|| version.map(ServerVersion::parse).unwrap_or_default()))
+    }
+
     pub async fn acquire_connection(&self) -> Result<Conn, Error> {
         self.pool.get_conn().await.map_err(Error::MySQLError)
     }
 
     pub async fn binlog_stream(&self, position: &BinlogPosition) -> Result<BinlogStream, Error> {
+        let version = self.server_version().await?;
         let mut connection = self.acquire_connection().await?;
 
-        let server_id = connection
+        let source_server_id: u32 = connection
             .query_first("SELECT @@server_id")
             .await?
             .unwrap_or(1);
 
-        let server_id = connection
-            .query_fold("SHOW SLAVE HOSTS", server_id, |init, row: Row| {
-                max(init, row.get::<u32, _>(0).unwrap_or(0))
+        let replica_ids: Vec<u32> = connection
+            .query_map(version.replicas_statement(), |row: Row| {
+                row.get::<u32, _>(0).unwrap_or(0)
             })
-            .await
-            .map_err(|_| Error::BinlogPositionMissing)?;
+            .await?;
+
+        let server_id = stream_server_id(
+            self.server_id,
+            source_server_id,
+            replica_ids,
+            self.stream_server_id.load(Ordering::Relaxed),
+        )?;
+
+        if version.is_mariadb() {
+            connection
+                .query_drop(format!(
+                    "SET @mariadb_slave_capability = {MARIADB_SLAVE_CAPABILITY}"
+                ))
+                .await?;
+        }
 
-        Ok(connection
+        if let Some(period) = self.heartbeat_period {
+            connection
+                .query_drop(format!(
+                    "SET @master_heartbeat_period = {}",
+                    period.as_nanos()
+                ))
+                .await?;
+        }
+
+        let stream = connection
             .get_binlog_stream(
-                BinlogRequest::new(server_id + 1)
+                BinlogRequest::new(server_id)
                     .with_filename(position.file().as_bytes())
                     .with_pos(position.position())
-                    .with_flags(self.dump_options),
+                    .with_flags(version.dump_flags(self.dump_options, self.annotate_rows)),
             )
-            .await?)
+            .await?;
+
+        self.stream_server_id.store(server_id, Ordering::Relaxed);
+
+        Ok(stream)
     }
 
     pub async fn binlog_position(&mut self) -> Result<BinlogPosition, Error> {
+        let version = self.server_version().await?;
+
         let row: Row = self
             .acquire_connection()
             .await?
-            .query_first("SHOW MASTER STATUS")
+            .query_first(version.binary_log_status_statement())
             .await?
             .ok_or(Error::BinlogPositionMissing)?;
 
@@ -82,4 +253,200 @@ impl Database {
             row.get(1).ok_or(Error::BinlogPositionMissing)?,
         ))
     }
+
+    pub async fn binlog_row_image(&self) -> Result<BinlogRowImage, Error> {
+        let value: Option<String> = self
+            .acquire_connection()
+            .await?
+            .query_first("SELECT @@binlog_row_image")
+            .await?;
+
+        Ok(value.map(BinlogRowImage::from_variable).unwrap_or_default())
+    }
+
+    /// Reads current row values by single column primary key
+    pub async fn lookup_row(
+        &self,
+        database_name: &str,
+        table_name: &str,
+        column: &str,
+        value: Value,
+    ) -> Result<Option<BinaryRow>, Error> {
+        let row: Option<Row> = self
+            .acquire_connection()
+            .await?
+            .exec_first(
+                format!("SELECT * FROM `{database_name}`.`{table_name}` WHERE `{column}` = ?"),
+                (value,),
+            )
+            .await?;
+
+        Ok(row.map(binary_row))
+    }
+
+    /// Reads current values of several rows by single column primary key in one query
+    pub async fn lookup_rows(
+        &self,
+        database_name: &str,
+        table_name: &str,
+        column: &str,
+        values: Vec<Value>,
+    ) -> Result<Vec<BinaryRow>, Error> {
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders = vec!["?"; values.len()].join(", ");
+        let rows: Vec<Row> = self
+            .acquire_connection()
+            .await?
+            .exec(
+                format!(
+                    "SELECT * FROM `{database_name}`.`{table_name}` WHERE `{column}` IN ({placeholders})"
+                ),
+                values,
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(binary_row).collect())
+    }
+}
+
+/// Picks server id for binlog stream that does not collide with the source server or other replicas
+///
+/// Source server can still list replica of the previous stream after connection loss,
+/// so explicit server id that was used by the previous stream is not a collision.
+fn stream_server_id(
+    server_id: Option<u32>,
+    source_server_id: u32,
+    replica_ids: Vec<u32>,
+    previous_server_id: u32,
+) -> Result<u32, Error> {
+    match server_id {
+        Some(server_id)
+            if server_id == source_server_id
+                || (server_id != previous_server_id && replica_ids.contains(&server_id)) =>
+        {
+            Err(Error::ServerIdCollision(server_id))
+        }
+        Some(server_id) => Ok(server_id),
+        None => Ok(replica_ids.into_iter().fold(source_server_id, max) + 1),
+    }
+}
+
+fn binary_row(row: Row) -> BinaryRow {
+    let values: Vec<_> = row
+        .unwrap()
+        .into_iter()
+        .map(|value| Some(BinlogValue::Value(value)))
+        .collect();
+
+    BinaryRow::new(&values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stream_server_id, ServerVersion};
+    use crate::error::Error;
+    use mysql_common::packets::BinlogDumpFlags;
+
+    #[test]
+    fn parses_mysql_and_mariadb_versions() {
+        assert_eq!(
+            vec![
+                ServerVersion::parse("8.0.33"),
+                ServerVersion::parse("5.7.42-log"),
+                ServerVersion::parse("10.11.2-MariaDB-1:10.11.2+maria~ubu2204"),
+                ServerVersion::parse("unknown"),
+            ],
+            vec![
+                ServerVersion::new(8, 0, 33),
+                ServerVersion::new(5, 7, 42),
+                ServerVersion::mariadb(10, 11, 2),
+                ServerVersion::default(),
+            ]
+        );
+    }
+
+    #[test]
+    fn picks_replicas_statement_by_server_version() {
+        assert_eq!(
+            vec![
+                ServerVersion::new(5, 7, 42).replicas_statement(),
+                ServerVersion::new(8, 0, 21).replicas_statement(),
+                ServerVersion::new(8, 0, 22).replicas_statement(),
+                ServerVersion::new(8, 4, 0).replicas_statement(),
+                ServerVersion::mariadb(10, 4, 28).replicas_statement(),
+                ServerVersion::mariadb(10, 6, 12).replicas_statement(),
+            ],
+            vec![
+                "SHOW SLAVE HOSTS",
+                "SHOW SLAVE HOSTS",
+                "SHOW REPLICAS",
+                "SHOW REPLICAS",
+                "SHOW SLAVE HOSTS",
+                "SHOW REPLICA HOSTS",
+            ]
+        );
+    }
+
+    #[test]
+    fn picks_binary_log_status_statement_by_server_version() {
+        assert_eq!(
+            vec![
+                ServerVersion::new(8, 0, 33).binary_log_status_statement(),
+                ServerVersion::new(8, 2, 0).binary_log_status_statement(),
+                ServerVersion::new(8, 4, 0).binary_log_status_statement(),
+                ServerVersion::mariadb(10, 4, 28).binary_log_status_statement(),
+                ServerVersion::mariadb(10, 11, 2).binary_log_status_statement(),
+            ],
+            vec![
+                "SHOW MASTER STATUS",
+                "SHOW BINARY LOG STATUS",
+                "SHOW BINARY LOG STATUS",
+                "SHOW MASTER STATUS",
+                "SHOW BINLOG STATUS",
+            ]
+        );
+    }
+
+    #[test]
+    fn requests_annotate_rows_events_only_from_mariadb() {
+        assert_eq!(
+            vec![
+                ServerVersion::mariadb(10, 11, 2)
+                    .dump_flags(BinlogDumpFlags::BINLOG_DUMP_NON_BLOCK, true)
+                    .bits(),
+                ServerVersion::mariadb(10, 11, 2)
+                    .dump_flags(BinlogDumpFlags::empty(), true)
+                    .bits(),
+                ServerVersion::mariadb(10, 11, 2)
+                    .dump_flags(BinlogDumpFlags::BINLOG_DUMP_NON_BLOCK, false)
+                    .bits(),
+                ServerVersion::new(8, 0, 33)
+                    .dump_flags(BinlogDumpFlags::BINLOG_DUMP_NON_BLOCK, true)
+                    .bits(),
+            ],
+            vec![0x03, 0x02, 0x01, 0x01]
+        );
+    }
+
+    #[test]
+    fn rejects_explicit_server_id_of_other_replica() {
+        assert!(matches!(
+            stream_server_id(Some(3), 1, vec![2, 3], 0),
+            Err(Error::ServerIdCollision(3))
+        ));
+    }
+
+    #[test]
+    fn reuses_explicit_server_id_of_previous_stream_still_listed_as_replica() {
+        assert_eq!(
+            vec![
+                stream_server_id(Some(3), 1, vec![2, 3], 3).ok(),
+                stream_server_id(None, 1, vec![2, 3], 4).ok(),
+            ],
+            vec![Some(3), Some(4)]
+        );
+    }
 }
@@ -23,4 +23,8 @@ pub enum Error {
     Synchronization,
     #[error("Binlog position cannot be found in database")]
     BinlogPositionMissing,
+    #[error("Server id {0} is already used by source server or another replica")]
+    ServerIdCollision(u32),
+    #[error("Tenant {0} uses the same database and table prefix as another one")]
+    AmbiguousTenant(String),
 }
@@ -1,9 +1,11 @@
-use crate::log::ProductChange;
+use crate::log::{ConfigChange, EntityChange, ProductChange};
 use crate::replication::EventMetadata;
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum ItemChange {
     ProductChange(ProductChange),
+    EntityChange(EntityChange),
+    ConfigChange(ConfigChange),
     Metadata(EventMetadata),
 }
 
@@ -13,6 +15,18 @@ impl From<ProductChange> for ItemChange {
     }
 }
 
+impl From<EntityChange> for ItemChange {
+    fn from(value: EntityChange) -> Self {
+        Self::EntityChange(value)
+    }
+}
+
+impl From<ConfigChange> for ItemChange {
+    fn from(value: ConfigChange) -> Self {
+        Self::ConfigChange(value)
+    }
+}
+
 impl From<EventMetadata> for ItemChange {
     fn from(value: EventMetadata) -> Self {
         Self::Metadata(value)
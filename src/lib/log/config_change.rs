@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+/// Old and new value of configuration path, `None` when the row did not exist
+pub type ConfigValue = (Option<Arc<str>>, Option<Arc<str>>);
+
+/// Change of configuration path in `core_config_data` for a scope
+///
+/// Old and new values are present only when they are allowed to be reported for the path.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ConfigChange {
+    path: Arc<str>,
+    scope: &'static str,
+    scope_id: usize,
+    value: Option<ConfigValue>,
+}
+
+impl ConfigChange {
+    pub fn new(path: impl Into<Arc<str>>, scope: &'static str, scope_id: usize) -> Self {
+        Self {
+            path: path.into(),
+            scope,
+            scope_id,
+            value: None,
+        }
+    }
+
+    pub fn with_value(self, old: Option<Arc<str>>, new: Option<Arc<str>>) -> Self {
+        Self {
+            value: Some((old, new)),
+            ..self
+        }
+    }
+
+    pub fn path(&self) -> &Arc<str> {
+        &self.path
+    }
+
+    pub fn scope(&self) -> (&'static str, usize) {
+        (self.scope, self.scope_id)
+    }
+
+    pub fn value(&self) -> Option<&ConfigValue> {
+        self.value.as_ref()
+    }
+
+    /// Name of the change type, used for reporting
+    pub fn kind(&self) -> &'static str {
+        "config"
+    }
+}
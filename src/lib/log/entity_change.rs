@@ -0,0 +1,48 @@
+use crate::aggregate::ChangeAggregateEntity;
+
+/// Change of entity other than product, reported by entity id under a key
+#[derive(PartialEq, Debug, Clone)]
+pub enum EntityChange {
+    Key(ChangeAggregateEntity, usize, &'static str),
+    ScopedKey(ChangeAggregateEntity, usize, &'static str, usize),
+    Attribute(ChangeAggregateEntity, usize, usize),
+}
+
+impl EntityChange {
+    /// Entity type this change belongs to
+    pub fn entity(&self) -> ChangeAggregateEntity {
+        match self {
+            Self::Key(entity, _, _)
+            | Self::ScopedKey(entity, _, _, _)
+            | Self::Attribute(entity, _, _) => *entity,
+        }
+    }
+
+    /// Id of the entity this change belongs to
+    pub fn entity_id(&self) -> usize {
+        match self {
+            Self::Key(_, entity_id, _)
+            | Self::ScopedKey(_, entity_id, _, _)
+            | Self::Attribute(_, entity_id, _) => *entity_id,
+        }
+    }
+
+    /// Same change for another entity
+    pub fn with_entity_id(mut self, id: usize) -> Self {
+        match &mut self {
+            Self::Key(_, entity_id, _)
+            | Self::ScopedKey(_, entity_id, _, _)
+            | Self::Attribute(_, entity_id, _) => *entity_id = id,
+        }
+
+        self
+    }
+
+    /// Key of the change without `@` prefix, used for reporting
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Key(_, _, key) | Self::ScopedKey(_, _, key, _) => key.trim_start_matches('@'),
+            Self::Attribute(_, _, _) => "attribute",
+        }
+    }
+}
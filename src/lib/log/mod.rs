@@ -1,9 +1,13 @@
 mod change_log;
+mod config_change;
+mod entity_change;
 mod field_update;
 mod product_change;
 mod sender;
 
 pub use change_log::ItemChange;
+pub use config_change::{ConfigChange, ConfigValue};
+pub use entity_change::EntityChange;
 pub use product_change::ProductChange;
 pub use sender::ChangeLogSender;
 
@@ -6,13 +6,107 @@ pub enum ProductChange {
     Deleted(usize),
     Created(usize),
     Fields(usize, SmallVec<[&'static str; MODIFIED_FIELDS_BUFFER_SIZE]>),
-    Attribute(usize, usize),
-    MediaGallery(usize),
+    Attribute(usize, usize, usize),
+    MediaGallery(usize, usize),
     LinkRelation(usize, usize),
     Website(usize, usize),
     Category(usize, usize),
     CompositeRelation(usize),
-    TierPrice(usize),
+    TierPrice(usize, usize, Option<usize>),
     Url(usize, usize),
     CategoryUrl(usize, usize),
+    Key(usize, &'static str),
+    ScopedKey(usize, &'static str, usize),
+    StagingUpdate(usize, usize),
+    SuperAttribute(usize),
+    Options(usize),
+    ChildChanged(usize),
+    IndexPrice(usize, usize, usize),
+    CatalogRule(usize, usize, usize),
+    CategoryPosition(usize, usize),
+}
+
+impl ProductChange {
+    /// Name of the change type, used for reporting
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Deleted(_) => "deleted",
+            Self::Created(_) => "created",
+            Self::Fields(_, _) => "fields",
+            Self::Attribute(_, _, _) => "attribute",
+            Self::MediaGallery(_, _) => "media_gallery",
+            Self::LinkRelation(_, _) => "link_relation",
+            Self::Website(_, _) => "website",
+            Self::Category(_, _) => "category",
+            Self::CompositeRelation(_) => "composite_relation",
+            Self::TierPrice(_, _, _) => "tier_price",
+            Self::Url(_, _) => "url",
+            Self::CategoryUrl(_, _) => "category_url",
+            Self::Key(_, _) => "key",
+            Self::ScopedKey(_, _, _) => "scoped_key",
+            Self::StagingUpdate(_, _) => "staging_update",
+            Self::SuperAttribute(_) => "super_attribute",
+            Self::Options(_) => "options",
+            Self::ChildChanged(_) => "child_changed",
+            Self::IndexPrice(_, _, _) => "index_price",
+            Self::CatalogRule(_, _, _) => "catalog_rule",
+            Self::CategoryPosition(_, _) => "category_position",
+        }
+    }
+
+    /// Id of the product this change belongs to
+    pub fn entity_id(&self) -> usize {
+        match self {
+            Self::Deleted(entity_id)
+            | Self::Created(entity_id)
+            | Self::Fields(entity_id, _)
+            | Self::Attribute(entity_id, _, _)
+            | Self::MediaGallery(entity_id, _)
+            | Self::LinkRelation(entity_id, _)
+            | Self::Website(entity_id, _)
+            | Self::Category(entity_id, _)
+            | Self::CompositeRelation(entity_id)
+            | Self::TierPrice(entity_id, _, _)
+            | Self::Url(entity_id, _)
+            | Self::CategoryUrl(entity_id, _)
+            | Self::Key(entity_id, _)
+            | Self::ScopedKey(entity_id, _, _)
+            | Self::StagingUpdate(entity_id, _)
+            | Self::SuperAttribute(entity_id)
+            | Self::Options(entity_id)
+            | Self::ChildChanged(entity_id)
+            | Self::IndexPrice(entity_id, _, _)
+            | Self::CatalogRule(entity_id, _, _)
+            | Self::CategoryPosition(entity_id, _) => *entity_id,
+        }
+    }
+
+    /// Same change for another product
+    pub fn with_entity_id(mut self, id: usize) -> Self {
+        match &mut self {
+            Self::Deleted(entity_id)
+            | Self::Created(entity_id)
+            | Self::Fields(entity_id, _)
+            | Self::Attribute(entity_id, _, _)
+            | Self::MediaGallery(entity_id, _)
+            | Self::LinkRelation(entity_id, _)
+            | Self::Website(entity_id, _)
+            | Self::Category(entity_id, _)
+            | Self::CompositeRelation(entity_id)
+            | Self::TierPrice(entity_id, _, _)
+            | Self::Url(entity_id, _)
+            | Self::CategoryUrl(entity_id, _)
+            | Self::Key(entity_id, _)
+            | Self::ScopedKey(entity_id, _, _)
+            | Self::StagingUpdate(entity_id, _)
+            | Self::SuperAttribute(entity_id)
+            | Self::Options(entity_id)
+            | Self::ChildChanged(entity_id)
+            | Self::IndexPrice(entity_id, _, _)
+            | Self::CatalogRule(entity_id, _, _)
+            | Self::CategoryPosition(entity_id, _) => *entity_id = id,
+        }
+
+        self
+    }
 }
@@ -0,0 +1,138 @@
+use crate::error::Error;
+use crate::log::ItemChange;
+use crate::mapper::product_category::ProductCategoryMapper;
+use crate::mapper::{
+    map_product_changes, CatalogRuleMapper, ChangeLogMapper, CmsMapper, EavMapper,
+    ProductAttributeMapper, ProductBundleMapper, ProductConfigurableMapper,
+    ProductIndexPriceMapper, ProductLinkMapper, ProductMapper, ProductMediaGalleryMapper,
+    ProductMediaGalleryValue, ProductStockMapper, ProductTierPriceMapper, ProductWebsite,
+    SalesMapper, StoreMapper,
+};
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Built-in mapper a table of platform database is dispatched to
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum TableMapper {
+    Product,
+    ProductAttribute,
+    ProductTierPrice,
+    ProductWebsite,
+    ProductCategory,
+    ProductLink,
+    ProductMediaGallery,
+    ProductMediaGalleryValue,
+    ProductBundle,
+    ProductConfigurable,
+    ProductIndexPrice,
+    ProductStock,
+    CatalogRule,
+    Cms,
+    Sales,
+    Store,
+    Eav,
+}
+
+impl TableMapper {
+    /// Mapper of tables with the same structure in Magento 2 and Magento 1 / OpenMage
+    pub(crate) fn shared(table_name: &str) -> Option<Self> {
+        Some(match table_name {
+            "catalog_product_entity" => Self::Product,
+            "catalog_product_entity_datetime"
+            | "catalog_product_entity_decimal"
+            | "catalog_product_entity_int"
+            | "catalog_product_entity_text"
+            | "catalog_product_entity_varchar" => Self::ProductAttribute,
+            "catalog_product_entity_tier_price" => Self::ProductTierPrice,
+            "catalog_product_website" => Self::ProductWebsite,
+            "catalog_category_product" => Self::ProductCategory,
+            "catalog_product_link" => Self::ProductLink,
+            "catalog_product_bundle_selection" => Self::ProductBundle,
+            "catalog_product_super_link" => Self::ProductConfigurable,
+            "catalog_product_index_price" => Self::ProductIndexPrice,
+            "cataloginventory_stock_item" => Self::ProductStock,
+            "catalogrule"
+            | "catalogrule_website"
+            | "catalogrule_customer_group"
+            | "catalogrule_product" => Self::CatalogRule,
+            "cms_page" | "cms_page_store" | "cms_block" | "cms_block_store" => Self::Cms,
+            "eav_attribute"
+            | "catalog_eav_attribute"
+            | "eav_attribute_label"
+            | "eav_attribute_option"
+            | "eav_attribute_option_value"
+            | "eav_attribute_set"
+            | "eav_attribute_group"
+            | "eav_entity_attribute" => Self::Eav,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn map_event_into(
+        self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Product => map_product_changes(&ProductMapper, event, schema, changes),
+            Self::ProductAttribute => {
+                map_product_changes(&ProductAttributeMapper, event, schema, changes)
+            }
+            Self::ProductTierPrice => {
+                map_product_changes(&ProductTierPriceMapper, event, schema, changes)
+            }
+            Self::ProductWebsite => map_product_changes(&ProductWebsite, event, schema, changes),
+            Self::ProductCategory => {
+                map_product_changes(&ProductCategoryMapper, event, schema, changes)
+            }
+            Self::ProductLink => map_product_changes(&ProductLinkMapper, event, schema, changes),
+            Self::ProductMediaGallery => {
+                map_product_changes(&ProductMediaGalleryMapper, event, schema, changes)
+            }
+            Self::ProductMediaGalleryValue => {
+                map_product_changes(&ProductMediaGalleryValue, event, schema, changes)
+            }
+            Self::ProductBundle => {
+                map_product_changes(&ProductBundleMapper, event, schema, changes)
+            }
+            Self::ProductConfigurable => {
+                map_product_changes(&ProductConfigurableMapper, event, schema, changes)
+            }
+            Self::ProductIndexPrice => {
+                map_product_changes(&ProductIndexPriceMapper, event, schema, changes)
+            }
+            Self::ProductStock => map_product_changes(&ProductStockMapper, event, schema, changes),
+            Self::CatalogRule => CatalogRuleMapper.map_event_into(event, schema, changes),
+            Self::Cms => CmsMapper.map_event_into(event, schema, changes),
+            Self::Sales => SalesMapper.map_event_into(event, schema, changes),
+            Self::Store => StoreMapper.map_event_into(event, schema, changes),
+            Self::Eav => EavMapper.map_event_into(event, schema, changes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatches_only_tables_shared_by_platforms() {
+        assert_eq!(
+            vec![
+                TableMapper::shared("catalog_product_entity_int"),
+                TableMapper::shared("cms_block_store"),
+                TableMapper::shared("cataloginventory_stock_item"),
+                TableMapper::shared("sales_order"),
+                TableMapper::shared("core_store"),
+            ],
+            vec![
+                Some(TableMapper::ProductAttribute),
+                Some(TableMapper::Cms),
+                Some(TableMapper::ProductStock),
+                None,
+                None
+            ]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::aggregate::ChangeAggregateEntity;
+use crate::error::Error;
+use crate::log::{EntityChange, ItemChange, ProductChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Mapper for catalog price rules
+///
+/// Changes of rules, their websites and customer groups are reported for catalog rule entity,
+/// so consumers can expand them into products. Rows of `catalogrule_product`
+/// are reported as product changes scoped by website and customer group.
+/// Website and customer group links of Adobe Commerce reference rule version by `row_id`
+/// instead of `rule_id`, they are resolved to the rule by `ContentStagingMapper`
+/// and skipped when mapped without it.
+pub struct CatalogRuleMapper;
+
+impl CatalogRuleMapper {
+    fn map_rule(
+        event: &Event,
+        schema: &impl TableSchema,
+        key: &'static str,
+        scope: Option<&str>,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        if !schema.has_column("rule_id") {
+            return Ok(());
+        }
+
+        let rules = event.map_owners(|row| {
+            let rule_id = row.parse("rule_id", schema)?;
+
+            Ok(match scope {
+                Some(scope) => EntityChange::ScopedKey(
+                    ChangeAggregateEntity::CatalogRule,
+                    rule_id,
+                    key,
+                    row.parse(scope, schema)?,
+                ),
+                None => EntityChange::Key(ChangeAggregateEntity::CatalogRule, rule_id, key),
+            })
+        })?;
+
+        changes.extend(rules.into_iter().map(ItemChange::EntityChange));
+
+        Ok(())
+    }
+
+    fn map_product(
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let products = event.map_owners(|row| {
+            Ok(ProductChange::CatalogRule(
+                row.parse("product_id", schema)?,
+                row.parse("website_id", schema)?,
+                row.parse("customer_group_id", schema)?,
+            ))
+        })?;
+
+        changes.extend(products.into_iter().map(ItemChange::ProductChange));
+
+        Ok(())
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for CatalogRuleMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match schema.table_name() {
+            "catalogrule" => Self::map_rule(event, schema, "@rule", None, changes),
+            "catalogrule_website" => {
+                Self::map_rule(event, schema, "@website", Some("website_id"), changes)
+            }
+            "catalogrule_customer_group" => Self::map_rule(
+                event,
+                schema,
+                "@customer_group",
+                Some("customer_group_id"),
+                changes,
+            ),
+            "catalogrule_product" => Self::map_product(event, schema, changes),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    #[test]
+    fn maps_rule_changes_into_catalog_rule_entity() {
+        mapper_test!(
+            CatalogRuleMapper,
+            Some(ItemChange::EntityChange(EntityChange::Key(
+                ChangeAggregateEntity::CatalogRule,
+                3,
+                "@rule"
+            ))),
+            Event::InsertRow(binlog_row!(3, "Summer sale", 1)),
+            test_table!("catalogrule", ["rule_id", "name", "is_active"])
+        );
+    }
+
+    #[test]
+    fn maps_rule_website_as_website_scoped_change() {
+        mapper_test!(
+            CatalogRuleMapper,
+            Some(ItemChange::EntityChange(EntityChange::ScopedKey(
+                ChangeAggregateEntity::CatalogRule,
+                3,
+                "@website",
+                2
+            ))),
+            Event::DeleteRow(binlog_row!(3, 2)),
+            test_table!("catalogrule_website", ["rule_id", "website_id"])
+        );
+    }
+
+    #[test]
+    fn maps_rule_customer_group_as_customer_group_scoped_change() {
+        mapper_test!(
+            CatalogRuleMapper,
+            Some(ItemChange::EntityChange(EntityChange::ScopedKey(
+                ChangeAggregateEntity::CatalogRule,
+                3,
+                "@customer_group",
+                1
+            ))),
+            Event::InsertRow(binlog_row!(3, 1)),
+            test_table!(
+                "catalogrule_customer_group",
+                ["rule_id", "customer_group_id"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_rule_moved_to_another_website_for_both_websites() {
+        mapper_changes_test!(
+            CatalogRuleMapper,
+            vec![
+                ItemChange::EntityChange(EntityChange::ScopedKey(
+                    ChangeAggregateEntity::CatalogRule,
+                    3,
+                    "@website",
+                    1
+                )),
+                ItemChange::EntityChange(EntityChange::ScopedKey(
+                    ChangeAggregateEntity::CatalogRule,
+                    3,
+                    "@website",
+                    2
+                ))
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(binlog_row!(3, 1), binlog_row!(3, 2))),
+            test_table!("catalogrule_website", ["rule_id", "website_id"])
+        );
+    }
+
+    #[test]
+    fn maps_rule_product_into_product_change() {
+        mapper_test!(
+            CatalogRuleMapper,
+            Some(ItemChange::ProductChange(ProductChange::CatalogRule(
+                10, 1, 0
+            ))),
+            Event::InsertRow(binlog_row!(1, 3, 0, 10, 1)),
+            test_table!(
+                "catalogrule_product",
+                [
+                    "rule_product_id",
+                    "rule_id",
+                    "customer_group_id",
+                    "product_id",
+                    "website_id"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn skips_rule_website_referencing_rule_version() {
+        mapper_test!(
+            CatalogRuleMapper,
+            None,
+            Event::InsertRow(binlog_row!(7, 2)),
+            test_table!("catalogrule_website", ["row_id", "website_id"])
+        );
+    }
+}
@@ -4,8 +4,15 @@ use crate::mapper::ChangeLogMapper;
 use crate::replication::Event;
 use crate::schema::TableSchema;
 
-
-pub struct ChainMapper<L, R>(L, R);
+/// Combines two mappers
+///
+/// Created with `new` it uses changes of the first mapper that produced any,
+/// created with `all` it collects changes of both mappers for every event.
+pub struct ChainMapper<L, R> {
+    left: L,
+    right: R,
+    all: bool,
+}
 
 impl<L, R> ChainMapper<L, R>
 where
@@ -13,7 +20,19 @@ where
     R: ChangeLogMapper<ItemChange>,
 {
     pub fn new(left: L, right: R) -> Self {
-        Self(left, right)
+        Self {
+            left,
+            right,
+            all: false,
+        }
+    }
+
+    pub fn all(left: L, right: R) -> Self {
+        Self {
+            left,
+            right,
+            all: true,
+        }
     }
 }
 
@@ -27,14 +46,166 @@ where
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ItemChange>, Error> {
-        if let Some(item) = self.0.map_event(event, schema)? {
+        if let Some(item) = self.left.map_event(event, schema)? {
             return Ok(Some(item));
         }
 
-        if let Some(item) = self.1.map_event(event, schema)? {
+        if let Some(item) = self.right.map_event(event, schema)? {
             return Ok(Some(item));
         }
 
         Ok(None)
     }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let length = changes.len();
+
+        self.left.map_event_into(event, schema, changes)?;
+
+        if self.all || changes.len() == length {
+            self.right.map_event_into(event, schema, changes)?;
+        }
+
+        Ok(())
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        self.left.is_mapped_table(table_name) || self.right.is_mapped_table(table_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::ProductChange;
+    use crate::mapper::{ConfigDataFilter, ConfigDataMapper, MagentoTwoMapper, TableMappingMapper};
+    use crate::test_util::TestTableSchema;
+
+    struct KeyMapper(&'static str);
+
+    impl ChangeLogMapper<ItemChange> for KeyMapper {
+        fn map_event(
+            &self,
+            event: &Event,
+            schema: &impl TableSchema,
+        ) -> Result<Option<ItemChange>, Error> {
+            Ok(match event {
+                Event::InsertRow(row) => Some(ItemChange::ProductChange(ProductChange::Key(
+                    row.parse("entity_id", schema)?,
+                    self.0,
+                ))),
+                _ => None,
+            })
+        }
+    }
+
+    struct FanOutMapper;
+
+    impl ChangeLogMapper<ItemChange> for FanOutMapper {
+        fn map_event(
+            &self,
+            event: &Event,
+            schema: &impl TableSchema,
+        ) -> Result<Option<ItemChange>, Error> {
+            KeyMapper("first").map_event(event, schema)
+        }
+
+        fn map_event_into(
+            &self,
+            event: &Event,
+            schema: &impl TableSchema,
+            changes: &mut Vec<ItemChange>,
+        ) -> Result<(), Error> {
+            KeyMapper("first").map_event_into(event, schema, changes)?;
+            KeyMapper("second").map_event_into(event, schema, changes)
+        }
+    }
+
+    fn map_all(
+        mapper: impl ChangeLogMapper<ItemChange>,
+        event: Event,
+        schema: TestTableSchema,
+    ) -> Vec<ItemChange> {
+        let mut changes = Vec::new();
+        mapper
+            .map_event_into(&event, &schema, &mut changes)
+            .unwrap();
+        changes
+    }
+
+    fn key_change(id: usize, key: &'static str) -> ItemChange {
+        ItemChange::ProductChange(ProductChange::Key(id, key))
+    }
+
+    #[test]
+    fn uses_changes_of_first_mapper_that_produced_any() {
+        assert_eq!(
+            map_all(
+                ChainMapper::new(KeyMapper("left"), KeyMapper("right")),
+                Event::InsertRow(binlog_row!(1)),
+                test_table!("entity", ["entity_id"])
+            ),
+            vec![key_change(1, "left")]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_second_mapper_without_changes_from_the_first() {
+        assert_eq!(
+            map_all(
+                ChainMapper::new(TableMappingMapper::default(), KeyMapper("right")),
+                Event::InsertRow(binlog_row!(1)),
+                test_table!("entity", ["entity_id"])
+            ),
+            vec![key_change(1, "right")]
+        );
+    }
+
+    #[test]
+    fn collects_changes_of_all_mappers() {
+        assert_eq!(
+            map_all(
+                ChainMapper::all(FanOutMapper, KeyMapper("right")),
+                Event::InsertRow(binlog_row!(2)),
+                test_table!("entity", ["entity_id"])
+            ),
+            vec![
+                key_change(2, "first"),
+                key_change(2, "second"),
+                key_change(2, "right")
+            ]
+        );
+    }
+
+    #[test]
+    fn returns_first_change_of_fan_out_mapper_as_single_change() {
+        mapper_test!(
+            ChainMapper::all(FanOutMapper, KeyMapper("right")),
+            Some(key_change(3, "first")),
+            insert[3, "value"],
+            ["entity_id", "value"]
+        );
+    }
+
+    #[test]
+    fn maps_tables_known_to_any_of_mappers() {
+        let mapper = ChainMapper::new(
+            MagentoTwoMapper,
+            Some(ConfigDataMapper::from(&ConfigDataFilter::default())),
+        );
+
+        assert_eq!(
+            vec![
+                mapper.is_mapped_table("catalog_product_entity_int"),
+                mapper.is_mapped_table("core_config_data"),
+                mapper.is_mapped_table("customer_grid_flat"),
+            ],
+            vec![true, true, false]
+        );
+    }
 }
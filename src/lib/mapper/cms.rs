@@ -0,0 +1,150 @@
+use crate::aggregate::ChangeAggregateEntity;
+use crate::error::Error;
+use crate::log::{EntityChange, ItemChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Mapper for CMS pages and blocks
+///
+/// Changes of pages and blocks are reported under `@page` and `@block` keys,
+/// while rows of their store link tables are reported scoped by store.
+/// Store links of Adobe Commerce reference page or block version by `row_id`,
+/// they are resolved to the page or block by `ContentStagingMapper`
+/// and skipped when mapped without it.
+pub struct CmsMapper;
+
+impl CmsMapper {
+    fn map_change(
+        event: &Event,
+        schema: &impl TableSchema,
+        id: &str,
+        key: &'static str,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        if !schema.has_column(id) {
+            return Ok(());
+        }
+
+        let owners = event.map_owners(|row| {
+            let entity_id = row.parse(id, schema)?;
+
+            Ok(match schema.has_column("store_id") {
+                true => EntityChange::ScopedKey(
+                    ChangeAggregateEntity::Cms,
+                    entity_id,
+                    key,
+                    row.parse("store_id", schema)?,
+                ),
+                false => EntityChange::Key(ChangeAggregateEntity::Cms, entity_id, key),
+            })
+        })?;
+
+        changes.extend(owners.into_iter().map(ItemChange::EntityChange));
+
+        Ok(())
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for CmsMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match schema.table_name() {
+            "cms_page" | "cms_page_store" => {
+                Self::map_change(event, schema, "page_id", "@page", changes)
+            }
+            "cms_block" | "cms_block_store" => {
+                Self::map_change(event, schema, "block_id", "@block", changes)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_page_changes() {
+        mapper_test!(
+            CmsMapper,
+            Some(ItemChange::EntityChange(EntityChange::Key(
+                ChangeAggregateEntity::Cms,
+                2,
+                "@page"
+            ))),
+            Event::InsertRow(binlog_row!(2, "About us", "about-us")),
+            test_table!("cms_page", ["page_id", "title", "identifier"])
+        );
+    }
+
+    #[test]
+    fn maps_page_store_changes_scoped_by_store() {
+        mapper_test!(
+            CmsMapper,
+            Some(ItemChange::EntityChange(EntityChange::ScopedKey(
+                ChangeAggregateEntity::Cms,
+                2,
+                "@page",
+                1
+            ))),
+            Event::DeleteRow(binlog_row!(2, 1)),
+            test_table!("cms_page_store", ["page_id", "store_id"])
+        );
+    }
+
+    #[test]
+    fn maps_block_changes() {
+        mapper_test!(
+            CmsMapper,
+            Some(ItemChange::EntityChange(EntityChange::Key(
+                ChangeAggregateEntity::Cms,
+                5,
+                "@block"
+            ))),
+            Event::InsertRow(binlog_row!(5, "Footer", "footer_links")),
+            test_table!("cms_block", ["block_id", "title", "identifier"])
+        );
+    }
+
+    #[test]
+    fn maps_block_store_changes_scoped_by_store() {
+        mapper_test!(
+            CmsMapper,
+            Some(ItemChange::EntityChange(EntityChange::ScopedKey(
+                ChangeAggregateEntity::Cms,
+                5,
+                "@block",
+                0
+            ))),
+            Event::InsertRow(binlog_row!(5, 0)),
+            test_table!("cms_block_store", ["block_id", "store_id"])
+        );
+    }
+
+    #[test]
+    fn skips_block_store_referencing_block_version() {
+        mapper_test!(
+            CmsMapper,
+            None,
+            Event::InsertRow(binlog_row!(12, 1)),
+            test_table!("cms_block_store", ["row_id", "store_id"])
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use crate::error::Error;
+use crate::log::{ConfigChange, ItemChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::{Event, EventRow};
+use crate::schema::TableSchema;
+
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// Parts of the last path segment that mark value as sensitive
+const SENSITIVE_PARTS: [&str; 6] = [
+    "password",
+    "passphrase",
+    "secret",
+    "key",
+    "token",
+    "credential",
+];
+
+/// Change of the row together with its value
+type ConfigRow = (ConfigChange, Option<Arc<str>>);
+
+/// Filter of configuration paths declared in application configuration
+///
+/// Paths are matched by whole segments, so `web/secure` matches `web/secure/base_url`
+/// but not `web/secure_cookie`. When `allow` is empty every path is reported, unless it is in `deny`.
+/// Values are included only with `values` enabled and never for paths that look sensitive,
+/// like passwords and keys, or for values encrypted by Magento.
+#[derive(Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct ConfigDataFilter {
+    #[serde(default)]
+    values: bool,
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+fn matches_path(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+
+    match path.strip_prefix(pattern) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+fn is_encrypted(value: &str) -> bool {
+    let mut parts = value.splitn(3, ':');
+
+    matches!(
+        (parts.next(), parts.next(), parts.next()),
+        (Some(key), Some(version), Some(_))
+            if !key.is_empty()
+                && !version.is_empty()
+                && key.bytes().chain(version.bytes()).all(|byte| byte.is_ascii_digit())
+    )
+}
+
+impl ConfigDataFilter {
+    pub fn with_values(self, values: bool) -> Self {
+        Self { values, ..self }
+    }
+
+    pub fn with_allow<I>(self, allow: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            allow: allow.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    pub fn with_deny<I>(self, deny: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            deny: deny.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    pub fn is_allowed(&self, path: &str) -> bool {
+        (self.allow.is_empty() || self.allow.iter().any(|pattern| matches_path(pattern, path)))
+            && !self.deny.iter().any(|pattern| matches_path(pattern, path))
+    }
+
+    pub fn is_value_allowed(&self, path: &str) -> bool {
+        let name = path.rsplit('/').next().unwrap_or(path);
+
+        self.values && !SENSITIVE_PARTS.iter().any(|part| name.contains(part))
+    }
+}
+
+/// Mapper for configuration values in `core_config_data`
+///
+/// Changed paths are reported for their scope, rows with unknown scope are ignored.
+/// Update that moves value to another path or scope is reported as removal and addition.
+pub struct ConfigDataMapper {
+    filter: ConfigDataFilter,
+}
+
+impl From<&ConfigDataFilter> for ConfigDataMapper {
+    fn from(filter: &ConfigDataFilter) -> Self {
+        Self {
+            filter: filter.clone(),
+        }
+    }
+}
+
+impl ConfigDataMapper {
+    fn map_row(
+        &self,
+        row: EventRow,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ConfigRow>, Error> {
+        let path: String = row.parse("path", schema)?;
+
+        if !self.filter.is_allowed(&path) {
+            return Ok(None);
+        }
+
+        let scope = match row.parse::<String>("scope", schema)?.as_str() {
+            "default" => "default",
+            "websites" => "websites",
+            "stores" => "stores",
+            _ => return Ok(None),
+        };
+
+        Ok(Some((
+            ConfigChange::new(path, scope, row.parse("scope_id", schema)?),
+            row.parse::<Option<String>>("value", schema)?
+                .map(Into::into),
+        )))
+    }
+
+    fn with_value(
+        &self,
+        change: ConfigChange,
+        old: Option<Arc<str>>,
+        new: Option<Arc<str>>,
+    ) -> ConfigChange {
+        let encrypted = [&old, &new]
+            .into_iter()
+            .any(|value| value.as_deref().is_some_and(is_encrypted));
+
+        match self.filter.is_value_allowed(change.path()) && !encrypted {
+            true => change.with_value(old, new),
+            false => change,
+        }
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for ConfigDataMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        if schema.table_name() != "core_config_data" {
+            return Ok(());
+        }
+
+        let mapped = match event {
+            Event::InsertRow(row) => vec![self
+                .map_row(EventRow::Row(row), schema)?
+                .map(|(change, value)| self.with_value(change, None, value))],
+            Event::DeleteRow(row) => vec![self
+                .map_row(EventRow::Row(row), schema)?
+                .map(|(change, value)| self.with_value(change, value, None))],
+            Event::UpdateRow(row)
+                if ["path", "scope", "scope_id"]
+                    .into_iter()
+                    .any(|column| row.is_changed_column(column, schema)) =>
+            {
+                vec![
+                    self.map_row(EventRow::Before(row), schema)?
+                        .map(|(change, value)| self.with_value(change, value, None)),
+                    self.map_row(EventRow::After(row), schema)?
+                        .map(|(change, value)| self.with_value(change, None, value)),
+                ]
+            }
+            Event::UpdateRow(row) if row.is_changed_column("value", schema) => {
+                let old = self
+                    .map_row(EventRow::Before(row), schema)?
+                    .and_then(|(_, value)| value);
+
+                vec![self
+                    .map_row(EventRow::After(row), schema)?
+                    .map(|(change, value)| self.with_value(change, old, value))]
+            }
+            Event::UpdateRow(_) => vec![],
+        };
+
+        changes.extend(mapped.into_iter().flatten().map(ItemChange::ConfigChange));
+
+        Ok(())
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        table_name == "core_config_data"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn mapper(filter: ConfigDataFilter) -> ConfigDataMapper {
+        ConfigDataMapper::from(&filter)
+    }
+
+    #[test]
+    fn maps_inserted_path_without_value_by_default() {
+        mapper_test!(
+            mapper(ConfigDataFilter::default()),
+            Some(ItemChange::ConfigChange(ConfigChange::new(
+                "web/secure/base_url",
+                "websites",
+                1
+            ))),
+            Event::InsertRow(binlog_row!(
+                1,
+                "websites",
+                1,
+                "web/secure/base_url",
+                "https://a/"
+            )),
+            test_table!(
+                "core_config_data",
+                ["config_id", "scope", "scope_id", "path", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_old_and_new_value_when_values_are_enabled() {
+        mapper_test!(
+            mapper(ConfigDataFilter::default().with_values(true)),
+            Some(ItemChange::ConfigChange(
+                ConfigChange::new("general/locale/code", "stores", 2)
+                    .with_value(Some("en_US".into()), Some("nl_NL".into()))
+            )),
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, "stores", 2, "general/locale/code", "en_US"),
+                binlog_row!(1, "stores", 2, "general/locale/code", "nl_NL")
+            )),
+            test_table!(
+                "core_config_data",
+                ["config_id", "scope", "scope_id", "path", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn omits_value_of_sensitive_path() {
+        mapper_test!(
+            mapper(ConfigDataFilter::default().with_values(true)),
+            Some(ItemChange::ConfigChange(ConfigChange::new(
+                "payment/braintree/private_key",
+                "default",
+                0
+            ))),
+            Event::InsertRow(binlog_row!(
+                1,
+                "default",
+                0,
+                "payment/braintree/private_key",
+                "abc"
+            )),
+            test_table!(
+                "core_config_data",
+                ["config_id", "scope", "scope_id", "path", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn omits_encrypted_value() {
+        mapper_test!(
+            mapper(ConfigDataFilter::default().with_values(true)),
+            Some(ItemChange::ConfigChange(ConfigChange::new(
+                "carriers/ups/username",
+                "default",
+                0
+            ))),
+            Event::DeleteRow(binlog_row!(
+                1,
+                "default",
+                0,
+                "carriers/ups/username",
+                "0:3:dXNlcg=="
+            )),
+            test_table!(
+                "core_config_data",
+                ["config_id", "scope", "scope_id", "path", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_update_without_value_change() {
+        mapper_test!(
+            mapper(ConfigDataFilter::default()),
+            None,
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(
+                    1,
+                    "default",
+                    0,
+                    "web/secure/base_url",
+                    "https://a/",
+                    "2024-01-01"
+                ),
+                binlog_row!(
+                    1,
+                    "default",
+                    0,
+                    "web/secure/base_url",
+                    "https://a/",
+                    "2024-01-02"
+                )
+            )),
+            test_table!(
+                "core_config_data",
+                [
+                    "config_id",
+                    "scope",
+                    "scope_id",
+                    "path",
+                    "value",
+                    "updated_at"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_moved_value_for_both_scopes() {
+        mapper_changes_test!(
+            mapper(ConfigDataFilter::default()),
+            vec![
+                ItemChange::ConfigChange(ConfigChange::new("web/secure/base_url", "default", 0)),
+                ItemChange::ConfigChange(ConfigChange::new("web/secure/base_url", "websites", 1))
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, "default", 0, "web/secure/base_url", "https://a/"),
+                binlog_row!(1, "websites", 1, "web/secure/base_url", "https://a/")
+            )),
+            test_table!(
+                "core_config_data",
+                ["config_id", "scope", "scope_id", "path", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn filters_paths_by_allow_and_deny_list() {
+        let filter = ConfigDataFilter::default()
+            .with_allow(["web", "payment/"])
+            .with_deny(["payment/checkmo"]);
+
+        assert_eq!(
+            [
+                "web/secure/base_url",
+                "web_extra/path",
+                "payment/free/active",
+                "payment/checkmo/active",
+                "general/locale/code"
+            ]
+            .map(|path| filter.is_allowed(path)),
+            [true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn detects_encrypted_values() {
+        assert_eq!(
+            ["0:3:c2VjcmV0", "1:2:abc", "12:30", "http://a:b:c"].map(is_encrypted),
+            [true, true, false, false]
+        );
+    }
+}
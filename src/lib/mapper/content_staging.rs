@@ -0,0 +1,415 @@
+use crate::error::Error;
+use crate::log::{ItemChange, ProductChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::{
+    is_row_version_relation_table, row_version_entity, RowIdResolver, TableSchema,
+};
+
+/// Adapts mappers to Adobe Commerce content staging
+///
+/// Tables keyed by `row_id` are mapped with `row_id` in place of the id column of their entity
+/// and changes are reported for the product or other entity the row version belongs to.
+/// Custom option, super attribute, composite and product link tables reference row version
+/// of the owning or parent product, so their changes are resolved the same way
+/// once database is known to have content staging.
+/// Rows of `catalog_product_entity` that start or end a staging update
+/// are reported as `ProductChange::StagingUpdate` instead of created or deleted product.
+pub struct ContentStagingMapper<M> {
+    mapper: M,
+    row_ids: RowIdResolver,
+}
+
+/// Table schema that resolves id column of the entity as `row_id`
+struct RowIdTableSchema<'a, S>(&'a S, &'static str);
+
+impl<S> TableSchema for RowIdTableSchema<'_, S>
+where
+    S: TableSchema,
+{
+    fn table_name(&self) -> &str {
+        self.0.table_name()
+    }
+
+    fn is_generated_primary_key(&self, column: impl AsRef<str>) -> bool {
+        self.0.is_generated_primary_key(column)
+    }
+
+    fn has_column(&self, column: impl AsRef<str>) -> bool {
+        match column.as_ref() {
+            column if column == self.1 => self.0.has_column("row_id"),
+            column => self.0.has_column(column),
+        }
+    }
+
+    fn column_position(&self, column: impl AsRef<str>) -> Option<usize> {
+        match column.as_ref() {
+            column if column == self.1 => self.0.column_position("row_id"),
+            column => self.0.column_position(column),
+        }
+    }
+}
+
+/// Version id of the first row of the product before any staging update
+const INITIAL_VERSION: usize = 1;
+
+impl<M> ContentStagingMapper<M>
+where
+    M: ChangeLogMapper<ItemChange>,
+{
+    pub fn new(mapper: M, row_ids: RowIdResolver) -> Self {
+        Self { mapper, row_ids }
+    }
+
+    fn staging_version(event: &Event, schema: &impl TableSchema) -> Result<Option<usize>, Error> {
+        if schema.table_name() != "catalog_product_entity" || !schema.has_column("created_in") {
+            return Ok(None);
+        }
+
+        Ok(match event {
+            Event::InsertRow(row) | Event::DeleteRow(row) => {
+                match row.parse("created_in", schema)? {
+                    INITIAL_VERSION => None,
+                    version => Some(version),
+                }
+            }
+            Event::UpdateRow(row) if row.is_changed_column("created_in", schema) => {
+                Some(row.parse_changed("created_in", schema)?)
+            }
+            Event::UpdateRow(row) if row.is_changed_column("updated_in", schema) => Some(
+                row.parse::<usize>("updated_in", schema)?
+                    .min(row.parse_changed("updated_in", schema)?),
+            ),
+            Event::UpdateRow(_) => None,
+        })
+    }
+
+    fn map_row_versions(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        entity_table: &'static str,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let mut row_changes = Vec::new();
+        self.mapper
+            .map_event_into(event, schema, &mut row_changes)?;
+
+        for change in row_changes {
+            match change {
+                ItemChange::ProductChange(change) => {
+                    if let Some(entity_id) = self.row_ids.resolve(change.entity_id()) {
+                        changes.push(ItemChange::ProductChange(change.with_entity_id(entity_id)));
+                    }
+                }
+                ItemChange::EntityChange(change) => {
+                    if let Some(entity_id) = self
+                        .row_ids
+                        .resolve_entity(entity_table, change.entity_id())
+                    {
+                        changes.push(ItemChange::EntityChange(change.with_entity_id(entity_id)));
+                    }
+                }
+                change => changes.push(change),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<M> ChangeLogMapper<ItemChange> for ContentStagingMapper<M>
+where
+    M: ChangeLogMapper<ItemChange>,
+{
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        if let Some((entity_table, id)) = row_version_entity(schema) {
+            return self.map_row_versions(
+                event,
+                &RowIdTableSchema(schema, id),
+                entity_table,
+                changes,
+            );
+        }
+
+        if self.row_ids.is_content_staging() && is_row_version_relation_table(schema.table_name()) {
+            return self.map_row_versions(event, schema, "catalog_product_entity", changes);
+        }
+
+        match (Self::staging_version(event, schema)?, event) {
+            (Some(version), Event::InsertRow(row) | Event::DeleteRow(row)) => {
+                changes.push(ItemChange::ProductChange(ProductChange::StagingUpdate(
+                    row.parse("entity_id", schema)?,
+                    version,
+                )));
+            }
+            (Some(version), Event::UpdateRow(row)) => {
+                changes.push(ItemChange::ProductChange(ProductChange::StagingUpdate(
+                    row.parse("entity_id", schema)?,
+                    version,
+                )));
+                self.mapper.map_event_into(event, schema, changes)?;
+            }
+            (None, _) => self.mapper.map_event_into(event, schema, changes)?,
+        }
+
+        Ok(())
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        self.mapper.is_mapped_table(table_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::ChangeAggregateEntity;
+    use crate::log::EntityChange;
+    use crate::mapper::{ChainMapper, MagentoTwoMapper, ProductRelationMapper};
+    use crate::replication::UpdateRowEvent;
+    use crate::schema::RelationIndex;
+    use crate::test_util::TestTableSchema;
+
+    const PRODUCT_TABLE: TestTableSchema = test_table!(
+        "catalog_product_entity",
+        ["row_id", "entity_id", "created_in", "updated_in", "sku"]
+    );
+
+    fn mapper() -> ContentStagingMapper<MagentoTwoMapper> {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert(10, 1);
+        row_ids.insert(11, 1);
+
+        ContentStagingMapper::new(MagentoTwoMapper, row_ids)
+    }
+
+    fn map_all(event: Event, schema: TestTableSchema) -> Vec<ItemChange> {
+        let mut changes = Vec::new();
+        mapper()
+            .map_event_into(&event, &schema, &mut changes)
+            .unwrap();
+        changes
+    }
+
+    #[test]
+    fn maps_attribute_row_version_into_product_change() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::Attribute(
+                1, 73, 0
+            ))),
+            Event::InsertRow(binlog_row!(11, 73, 0, "Name")),
+            test_table!(
+                "catalog_product_entity_varchar",
+                ["row_id", "attribute_id", "store_id", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn skips_changes_of_unknown_row_versions() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(12, 73, 0, "Name")),
+            test_table!(
+                "catalog_product_entity_varchar",
+                ["row_id", "attribute_id", "store_id", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_initial_product_row_as_created_product() {
+        assert_eq!(
+            map_all(
+                Event::InsertRow(binlog_row!(10, 1, 1, 2147483647, "SKU1")),
+                PRODUCT_TABLE
+            ),
+            vec![ItemChange::ProductChange(ProductChange::Created(1))]
+        );
+    }
+
+    #[test]
+    fn maps_row_version_of_staging_update_as_staging_change() {
+        assert_eq!(
+            map_all(
+                Event::InsertRow(binlog_row!(11, 1, 1700000000, 2147483647, "SKU1")),
+                PRODUCT_TABLE
+            ),
+            vec![ItemChange::ProductChange(ProductChange::StagingUpdate(
+                1, 1700000000
+            ))]
+        );
+    }
+
+    #[test]
+    fn maps_scheduled_end_of_row_version_as_staging_change() {
+        assert_eq!(
+            map_all(
+                Event::UpdateRow(UpdateRowEvent::new(
+                    binlog_row!(10, 1, 1, 2147483647, "SKU1"),
+                    binlog_row!(10, 1, 1, 1700000000, "SKU1"),
+                )),
+                PRODUCT_TABLE
+            ),
+            vec![ItemChange::ProductChange(ProductChange::StagingUpdate(
+                1, 1700000000
+            ))]
+        );
+    }
+
+    #[test]
+    fn maps_option_of_row_version_into_product_change() {
+        let relations = RelationIndex::default();
+        let row_ids = RowIdResolver::default();
+        row_ids.insert(11, 1);
+        row_ids.set_content_staging();
+
+        mapper_test!(
+            ContentStagingMapper::new(
+                ChainMapper::new(MagentoTwoMapper, ProductRelationMapper::new(relations)),
+                row_ids
+            ),
+            Some(ItemChange::ProductChange(ProductChange::Options(1))),
+            Event::InsertRow(binlog_row!(4, 11, "field")),
+            test_table!(
+                "catalog_product_option",
+                ["option_id", "product_id", "type"]
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_option_product_without_content_staging() {
+        mapper_test!(
+            ContentStagingMapper::new(
+                ProductRelationMapper::new(RelationIndex::default()),
+                RowIdResolver::default()
+            ),
+            Some(ItemChange::ProductChange(ProductChange::Options(11))),
+            Event::InsertRow(binlog_row!(4, 11, "field")),
+            test_table!(
+                "catalog_product_option",
+                ["option_id", "product_id", "type"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_configurable_parent_row_version_into_product_change() {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert(11, 1);
+        row_ids.set_content_staging();
+
+        mapper_test!(
+            ContentStagingMapper::new(MagentoTwoMapper, row_ids),
+            Some(ItemChange::ProductChange(ProductChange::CompositeRelation(
+                1
+            ))),
+            Event::InsertRow(binlog_row!(1, 4, 11)),
+            test_table!(
+                "catalog_product_super_link",
+                ["link_id", "product_id", "parent_id"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_bundle_parent_row_version_into_product_change() {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert(11, 1);
+        row_ids.set_content_staging();
+
+        mapper_test!(
+            ContentStagingMapper::new(MagentoTwoMapper, row_ids),
+            Some(ItemChange::ProductChange(ProductChange::CompositeRelation(
+                1
+            ))),
+            Event::InsertRow(binlog_row!(1, 2, 11, 4)),
+            test_table!(
+                "catalog_product_bundle_selection",
+                [
+                    "selection_id",
+                    "option_id",
+                    "parent_product_id",
+                    "product_id"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_rule_website_row_version_into_rule_change() {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert_entity("catalogrule", 7, 3);
+        row_ids.insert(7, 1);
+
+        mapper_test!(
+            ContentStagingMapper::new(MagentoTwoMapper, row_ids),
+            Some(ItemChange::EntityChange(EntityChange::ScopedKey(
+                ChangeAggregateEntity::CatalogRule,
+                3,
+                "@website",
+                2
+            ))),
+            Event::InsertRow(binlog_row!(7, 2)),
+            test_table!("catalogrule_website", ["row_id", "website_id"])
+        );
+    }
+
+    #[test]
+    fn maps_block_store_row_version_into_block_change() {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert_entity("cms_block", 12, 5);
+
+        mapper_test!(
+            ContentStagingMapper::new(MagentoTwoMapper, row_ids),
+            Some(ItemChange::EntityChange(EntityChange::ScopedKey(
+                ChangeAggregateEntity::Cms,
+                5,
+                "@block",
+                1
+            ))),
+            Event::InsertRow(binlog_row!(12, 1)),
+            test_table!("cms_block_store", ["row_id", "store_id"])
+        );
+    }
+
+    #[test]
+    fn skips_page_store_of_unknown_page_version() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(12, 1)),
+            test_table!("cms_page_store", ["row_id", "store_id"])
+        );
+    }
+
+    #[test]
+    fn keeps_tables_without_row_id_intact() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::Website(11, 1))),
+            Event::InsertRow(binlog_row!(11, 1)),
+            test_table!("catalog_product_website", ["product_id", "website_id"])
+        );
+    }
+}
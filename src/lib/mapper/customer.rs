@@ -0,0 +1,339 @@
+use crate::aggregate::ChangeAggregateEntity;
+use crate::error::Error;
+use crate::log::{EntityChange, ItemChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::{Relation, RelationIndex, TableSchema};
+
+/// Columns of `customer_entity` reported as field changes
+///
+/// Timestamps, password and lock related columns are left out,
+/// as they change on every login without any change in customer data.
+const CUSTOMER_FIELDS: [&str; 16] = [
+    "email",
+    "group_id",
+    "website_id",
+    "store_id",
+    "is_active",
+    "prefix",
+    "firstname",
+    "middlename",
+    "lastname",
+    "suffix",
+    "dob",
+    "gender",
+    "taxvat",
+    "default_billing",
+    "default_shipping",
+    "disable_auto_group_change",
+];
+
+/// Relation of addresses to the customer they belong to
+static CUSTOMER_ADDRESS: Relation = Relation {
+    table: "customer_address_entity",
+    id: "entity_id",
+    owner: "parent_id",
+    parent: None,
+    condition: None,
+};
+
+/// Attribute tables of addresses, referencing address by `entity_id`
+const ADDRESS_ATTRIBUTE_TABLES: [&str; 5] = [
+    "customer_address_entity_datetime",
+    "customer_address_entity_decimal",
+    "customer_address_entity_int",
+    "customer_address_entity_text",
+    "customer_address_entity_varchar",
+];
+
+/// Mapper for customers and their addresses
+///
+/// Changes of customers are reported under `@created`, `@deleted`, field and attribute keys,
+/// while changes of addresses and their attributes are reported under `@address` key
+/// for the customer that owns the address, resolved with `schema::RelationIndex`.
+pub struct CustomerMapper {
+    addresses: RelationIndex,
+}
+
+impl CustomerMapper {
+    pub fn new(addresses: RelationIndex) -> Self {
+        for table_name in ADDRESS_ATTRIBUTE_TABLES {
+            addresses.register(table_name, "entity_id", &CUSTOMER_ADDRESS);
+        }
+
+        Self { addresses }
+    }
+
+    fn map_customer(
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let change =
+            |key, id| ItemChange::from(EntityChange::Key(ChangeAggregateEntity::Customer, id, key));
+
+        match event {
+            Event::InsertRow(row) => {
+                changes.push(change("@created", row.parse("entity_id", schema)?))
+            }
+            Event::DeleteRow(row) => {
+                changes.push(change("@deleted", row.parse("entity_id", schema)?))
+            }
+            Event::UpdateRow(row) if row.is_changed_column("entity_id", schema) => {
+                changes.push(change("@deleted", row.parse("entity_id", schema)?));
+                changes.push(change("@created", row.parse_changed("entity_id", schema)?));
+            }
+            Event::UpdateRow(row) => {
+                let entity_id = row.parse("entity_id", schema)?;
+
+                changes.extend(
+                    CUSTOMER_FIELDS
+                        .into_iter()
+                        .filter(|field| row.is_changed_column(field, schema))
+                        .map(|field| change(field, entity_id)),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn map_attribute(
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let owners = event.map_owners(|row| {
+            Ok(EntityChange::Attribute(
+                ChangeAggregateEntity::Customer,
+                row.parse("entity_id", schema)?,
+                row.parse("attribute_id", schema)?,
+            ))
+        })?;
+
+        changes.extend(owners.into_iter().map(ItemChange::EntityChange));
+
+        Ok(())
+    }
+
+    fn map_address(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let mut customers = match schema.table_name() {
+            "customer_address_entity" => event.map_owners(|row| row.parse("parent_id", schema))?,
+            _ => event
+                .map_owners(|row| row.parse("entity_id", schema))?
+                .into_iter()
+                .filter_map(|id| self.addresses.resolve(&CUSTOMER_ADDRESS, id))
+                .collect(),
+        };
+        customers.dedup();
+
+        changes.extend(customers.into_iter().map(|customer_id| {
+            ItemChange::from(EntityChange::Key(
+                ChangeAggregateEntity::Customer,
+                customer_id,
+                "@address",
+            ))
+        }));
+
+        Ok(())
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for CustomerMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match schema.table_name() {
+            "customer_entity" => Self::map_customer(event, schema, changes),
+            "customer_entity_datetime"
+            | "customer_entity_decimal"
+            | "customer_entity_int"
+            | "customer_entity_text"
+            | "customer_entity_varchar" => Self::map_attribute(event, schema, changes),
+            "customer_address_entity"
+            | "customer_address_entity_datetime"
+            | "customer_address_entity_decimal"
+            | "customer_address_entity_int"
+            | "customer_address_entity_text"
+            | "customer_address_entity_varchar" => self.map_address(event, schema, changes),
+            // Grid index is rebuilt from the tables above, its rows would only duplicate changes
+            "customer_grid_flat" => Ok(()),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        matches!(
+            table_name,
+            "customer_entity"
+                | "customer_entity_datetime"
+                | "customer_entity_decimal"
+                | "customer_entity_int"
+                | "customer_entity_text"
+                | "customer_entity_varchar"
+                | "customer_address_entity"
+                | "customer_address_entity_datetime"
+                | "customer_address_entity_decimal"
+                | "customer_address_entity_int"
+                | "customer_address_entity_text"
+                | "customer_address_entity_varchar"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn mapper() -> CustomerMapper {
+        let addresses = RelationIndex::default();
+        addresses.insert(&CUSTOMER_ADDRESS, 7, 3);
+
+        CustomerMapper::new(addresses)
+    }
+
+    fn customer_change(key: &'static str, id: usize) -> ItemChange {
+        ItemChange::EntityChange(EntityChange::Key(ChangeAggregateEntity::Customer, id, key))
+    }
+
+    #[test]
+    fn maps_created_customer() {
+        mapper_test!(
+            mapper(),
+            Some(customer_change("@created", 3)),
+            Event::InsertRow(binlog_row!(3, "john@example.com", 1)),
+            test_table!("customer_entity", ["entity_id", "email", "website_id"])
+        );
+    }
+
+    #[test]
+    fn maps_deleted_customer() {
+        mapper_test!(
+            mapper(),
+            Some(customer_change("@deleted", 3)),
+            Event::DeleteRow(binlog_row!(3, "john@example.com", 1)),
+            test_table!("customer_entity", ["entity_id", "email", "website_id"])
+        );
+    }
+
+    #[test]
+    fn maps_changed_fields_of_customer_ignoring_timestamps() {
+        mapper_changes_test!(
+            mapper(),
+            vec![customer_change("email", 3), customer_change("firstname", 3)],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(3, "john@example.com", "John", "2024-01-01 00:00:00"),
+                binlog_row!(3, "jane@example.com", "Jane", "2024-01-02 00:00:00")
+            )),
+            test_table!(
+                "customer_entity",
+                ["entity_id", "email", "firstname", "updated_at"]
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_customer_update_of_timestamps_only() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(3, "john@example.com", "2024-01-01 00:00:00"),
+                binlog_row!(3, "john@example.com", "2024-01-02 00:00:00")
+            )),
+            test_table!("customer_entity", ["entity_id", "email", "updated_at"])
+        );
+    }
+
+    #[test]
+    fn maps_customer_attribute_change() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::EntityChange(EntityChange::Attribute(
+                ChangeAggregateEntity::Customer,
+                3,
+                11
+            ))),
+            Event::InsertRow(binlog_row!(1, 11, 3, "value")),
+            test_table!(
+                "customer_entity_varchar",
+                ["value_id", "attribute_id", "entity_id", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_address_moved_to_another_customer_for_both_customers() {
+        mapper_changes_test!(
+            mapper(),
+            vec![
+                customer_change("@address", 3),
+                customer_change("@address", 4)
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(7, 3, "Main street"),
+                binlog_row!(7, 4, "Main street")
+            )),
+            test_table!(
+                "customer_address_entity",
+                ["entity_id", "parent_id", "street"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_address_attribute_to_owning_customer() {
+        mapper_test!(
+            mapper(),
+            Some(customer_change("@address", 3)),
+            Event::InsertRow(binlog_row!(1, 20, 7, "value")),
+            test_table!(
+                "customer_address_entity_varchar",
+                ["value_id", "attribute_id", "entity_id", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_address_attribute_of_unknown_address() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(1, 20, 8, "value")),
+            test_table!(
+                "customer_address_entity_varchar",
+                ["value_id", "attribute_id", "entity_id", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_customer_grid_index() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(3, "John Doe", "john@example.com")),
+            test_table!("customer_grid_flat", ["entity_id", "name", "email"])
+        );
+    }
+}
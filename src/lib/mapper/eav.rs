@@ -0,0 +1,149 @@
+use crate::aggregate::ChangeAggregateEntity;
+use crate::error::Error;
+use crate::log::{EntityChange, ItemChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Columns of EAV schema table with the key their ids are reported under
+type EavKeys = &'static [(&'static str, &'static str)];
+
+/// Mapper for EAV schema of attributes, attribute options and attribute sets
+///
+/// Changed attribute ids are reported under `@attribute`, option ids under `@option`
+/// and attribute set ids under `@attribute_set` key. Rows that link them together,
+/// like attribute in a set or option of an attribute, are reported for both sides.
+pub struct EavMapper;
+
+fn table_keys(table_name: &str) -> Option<EavKeys> {
+    Some(match table_name {
+        "eav_attribute" | "catalog_eav_attribute" | "eav_attribute_label" => {
+            &[("attribute_id", "@attribute")]
+        }
+        "eav_attribute_option" => &[("option_id", "@option"), ("attribute_id", "@attribute")],
+        "eav_attribute_option_value" | "eav_attribute_option_swatch" => &[("option_id", "@option")],
+        "eav_attribute_set" | "eav_attribute_group" => &[("attribute_set_id", "@attribute_set")],
+        "eav_entity_attribute" => &[
+            ("attribute_set_id", "@attribute_set"),
+            ("attribute_id", "@attribute"),
+        ],
+        _ => return None,
+    })
+}
+
+impl ChangeLogMapper<ItemChange> for EavMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let keys = match table_keys(schema.table_name()) {
+            Some(keys) => keys,
+            None => return Ok(()),
+        };
+
+        for (column, key) in keys {
+            let owners = event.map_owners(|row| {
+                Ok(EntityChange::Key(
+                    ChangeAggregateEntity::Eav,
+                    row.parse(column, schema)?,
+                    key,
+                ))
+            })?;
+
+            changes.extend(owners.into_iter().map(ItemChange::EntityChange));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn eav_change(key: &'static str, id: usize) -> ItemChange {
+        ItemChange::EntityChange(EntityChange::Key(ChangeAggregateEntity::Eav, id, key))
+    }
+
+    #[test]
+    fn maps_attribute_change() {
+        mapper_test!(
+            EavMapper,
+            Some(eav_change("@attribute", 73)),
+            Event::InsertRow(binlog_row!(73, 1, 1)),
+            test_table!(
+                "catalog_eav_attribute",
+                ["attribute_id", "is_filterable", "is_searchable"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_new_option_for_option_and_attribute() {
+        mapper_changes_test!(
+            EavMapper,
+            vec![eav_change("@option", 5), eav_change("@attribute", 93)],
+            Event::InsertRow(binlog_row!(5, 93, 0)),
+            test_table!(
+                "eav_attribute_option",
+                ["option_id", "attribute_id", "sort_order"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_option_label_change_to_option() {
+        mapper_test!(
+            EavMapper,
+            Some(eav_change("@option", 5)),
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 5, 0, "Red"),
+                binlog_row!(1, 5, 0, "Dark Red")
+            )),
+            test_table!(
+                "eav_attribute_option_value",
+                ["value_id", "option_id", "store_id", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_attribute_moved_between_sets_for_both_sets() {
+        mapper_changes_test!(
+            EavMapper,
+            vec![
+                eav_change("@attribute_set", 4),
+                eav_change("@attribute_set", 9),
+                eav_change("@attribute", 73)
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 4, 4, 7, 73),
+                binlog_row!(1, 4, 9, 12, 73)
+            )),
+            test_table!(
+                "eav_entity_attribute",
+                [
+                    "entity_attribute_id",
+                    "entity_type_id",
+                    "attribute_set_id",
+                    "attribute_group_id",
+                    "attribute_id"
+                ]
+            )
+        );
+    }
+}
@@ -1,54 +1,58 @@
 use crate::error::Error;
 use crate::log::ItemChange;
-use crate::log::ItemChange::ProductChange;
-use crate::mapper::product_category::ProductCategoryMapper;
-use crate::mapper::{
-    ChangeLogMapper, ProductAttributeMapper, ProductBundleMapper, ProductConfigurableMapper,
-    ProductLinkMapper, ProductMapper, ProductMediaGalleryValue, ProductTierPriceMapper,
-    ProductWebsite,
-};
+use crate::mapper::builtin::TableMapper;
+use crate::mapper::ChangeLogMapper;
 use crate::replication::Event;
 use crate::schema::TableSchema;
 
 pub struct MagentoTwoMapper;
 
+impl MagentoTwoMapper {
+    fn table_mapper(table_name: &str) -> Option<TableMapper> {
+        Some(match table_name {
+            "catalog_product_entity_media_gallery_value" => TableMapper::ProductMediaGalleryValue,
+            "sales_order"
+            | "sales_order_item"
+            | "sales_order_address"
+            | "sales_order_status_history"
+            | "sales_invoice"
+            | "sales_shipment"
+            | "sales_creditmemo"
+            | "quote"
+            | "quote_item"
+            | "quote_address" => TableMapper::Sales,
+            "store" | "store_group" | "store_website" => TableMapper::Store,
+            "eav_attribute_option_swatch" => TableMapper::Eav,
+            table_name => return TableMapper::shared(table_name),
+        })
+    }
+}
+
 impl ChangeLogMapper<ItemChange> for MagentoTwoMapper {
     fn map_event(
         &self,
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ItemChange>, Error> {
-        Ok(match schema.table_name() {
-            "catalog_product_entity" => ProductMapper.map_event(event, schema)?.map(ProductChange),
-            "catalog_product_entity_datetime"
-            | "catalog_product_entity_decimal"
-            | "catalog_product_entity_int"
-            | "catalog_product_entity_text"
-            | "catalog_product_entity_varchar" => ProductAttributeMapper
-                .map_event(event, schema)?
-                .map(ProductChange),
-            "catalog_product_entity_tier_price" => ProductTierPriceMapper
-                .map_event(event, schema)?
-                .map(ProductChange),
-            "catalog_product_website" => {
-                ProductWebsite.map_event(event, schema)?.map(ProductChange)
-            }
-            "catalog_category_product" => ProductCategoryMapper
-                .map_event(event, schema)?
-                .map(ProductChange),
-            "catalog_product_link" => ProductLinkMapper
-                .map_event(event, schema)?
-                .map(ProductChange),
-            "catalog_product_entity_media_gallery_value" => ProductMediaGalleryValue
-                .map_event(event, schema)?
-                .map(ProductChange),
-            "catalog_product_bundle_selection" => ProductBundleMapper
-                .map_event(event, schema)?
-                .map(ProductChange),
-            "catalog_product_super_link" => ProductConfigurableMapper
-                .map_event(event, schema)?
-                .map(ProductChange),
-            _ => None,
-        })
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match Self::table_mapper(schema.table_name()) {
+            Some(mapper) => mapper.map_event_into(event, schema, changes),
+            None => Ok(()),
+        }
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        Self::table_mapper(table_name).is_some()
     }
 }
@@ -1,31 +1,170 @@
+mod builtin;
+mod catalog_rule;
 mod chain;
+mod cms;
+mod config_data;
+mod content_staging;
+mod customer;
+mod eav;
 mod magento_two;
 mod observer;
+mod open_mage;
+mod parent_propagation;
+mod platform;
 mod product;
 mod product_attribute;
 mod product_bundle;
 mod product_category;
 mod product_configurable;
+mod product_index_price;
 mod product_link;
+mod product_media_gallery;
+mod product_media_gallery_entity;
 mod product_media_gallery_value;
+mod product_relation;
+mod product_review;
+mod product_stock;
 mod product_tier_price;
 mod product_website;
+mod sales;
+mod store;
+mod table_mapping;
 
 use crate::error::Error;
+use crate::log::{ItemChange, ProductChange};
 use crate::replication::Event;
 use crate::schema::TableSchema;
+use std::sync::Arc;
+pub use catalog_rule::CatalogRuleMapper;
 pub use chain::ChainMapper;
+pub use cms::CmsMapper;
+pub use config_data::{ConfigDataFilter, ConfigDataMapper};
+pub use content_staging::ContentStagingMapper;
+pub use customer::CustomerMapper;
+pub use eav::EavMapper;
 pub use magento_two::MagentoTwoMapper;
 pub use observer::MapperObserver;
+pub use open_mage::OpenMageMapper;
+pub use parent_propagation::ParentPropagationMapper;
+pub use platform::{Platform, PlatformMapper};
 pub use product::ProductMapper;
 pub use product_attribute::ProductAttributeMapper;
 pub use product_bundle::ProductBundleMapper;
 pub use product_configurable::ProductConfigurableMapper;
+pub use product_index_price::ProductIndexPriceMapper;
 pub use product_link::ProductLinkMapper;
+pub use product_media_gallery::ProductMediaGalleryMapper;
+pub use product_media_gallery_entity::ProductMediaGalleryEntityMapper;
 pub use product_media_gallery_value::ProductMediaGalleryValue;
+pub use product_relation::ProductRelationMapper;
+pub use product_review::ProductReviewMapper;
+pub use product_stock::ProductStockMapper;
 pub use product_tier_price::ProductTierPriceMapper;
 pub use product_website::ProductWebsite;
+pub use sales::SalesMapper;
+pub use store::StoreMapper;
+pub use table_mapping::{TableMapping, TableMappingMapper};
 
 pub trait ChangeLogMapper<T> {
     fn map_event(&self, event: &Event, schema: &impl TableSchema) -> Result<Option<T>, Error>;
+
+    /// Maps event into every change it produces
+    ///
+    /// By default it is a single change from `map_event`,
+    /// mappers that produce multiple changes for one row override it.
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<T>,
+    ) -> Result<(), Error> {
+        changes.extend(self.map_event(event, schema)?);
+        Ok(())
+    }
+
+    /// Checks if events of the table can produce changes
+    ///
+    /// Rows of tables that are not mapped are not looked up when binlog row image is lossy.
+    /// By default every table is considered mapped.
+    fn is_mapped_table(&self, _table_name: &str) -> bool {
+        true
+    }
+}
+
+/// Shares one mapper between databases of several tenants
+impl<T, M> ChangeLogMapper<T> for Arc<M>
+where
+    M: ChangeLogMapper<T>,
+{
+    fn map_event(&self, event: &Event, schema: &impl TableSchema) -> Result<Option<T>, Error> {
+        self.as_ref().map_event(event, schema)
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<T>,
+    ) -> Result<(), Error> {
+        self.as_ref().map_event_into(event, schema, changes)
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        self.as_ref().is_mapped_table(table_name)
+    }
+}
+
+/// Mapper enabled only for some configurations, disabled one produces no changes
+impl<T, M> ChangeLogMapper<T> for Option<M>
+where
+    M: ChangeLogMapper<T>,
+{
+    fn map_event(&self, event: &Event, schema: &impl TableSchema) -> Result<Option<T>, Error> {
+        match self {
+            Some(mapper) => mapper.map_event(event, schema),
+            None => Ok(None),
+        }
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<T>,
+    ) -> Result<(), Error> {
+        match self {
+            Some(mapper) => mapper.map_event_into(event, schema, changes),
+            None => Ok(()),
+        }
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        self.as_ref()
+            .is_some_and(|mapper| mapper.is_mapped_table(table_name))
+    }
+}
+
+/// No mapper, used in place of custom mappers until any is added
+impl<T> ChangeLogMapper<T> for () {
+    fn map_event(&self, _event: &Event, _schema: &impl TableSchema) -> Result<Option<T>, Error> {
+        Ok(None)
+    }
+
+    fn is_mapped_table(&self, _table_name: &str) -> bool {
+        false
+    }
+}
+
+/// Maps event with product mapper into every item change it produces
+pub(crate) fn map_product_changes(
+    mapper: &impl ChangeLogMapper<ProductChange>,
+    event: &Event,
+    schema: &impl TableSchema,
+    changes: &mut Vec<ItemChange>,
+) -> Result<(), Error> {
+    let mut product_changes = Vec::new();
+    mapper.map_event_into(event, schema, &mut product_changes)?;
+    changes.extend(product_changes.into_iter().map(ItemChange::ProductChange));
+
+    Ok(())
 }
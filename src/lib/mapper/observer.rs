@@ -26,9 +26,11 @@ where
     S: ChangeLogSender<Item = ItemChange>,
 {
     async fn process_event(&self, event: &Event, table: &impl TableSchema) -> Result<(), Error> {
-        match self.mapper.map_event(event, table)? {
-            Some(change) => self.sender.send(change).await?,
-            None => (),
+        let mut changes = Vec::new();
+        self.mapper.map_event_into(event, table, &mut changes)?;
+
+        for change in changes {
+            self.sender.send(change).await?;
         }
 
         Ok(())
@@ -37,4 +39,8 @@ where
     async fn process_metadata(&self, metadata: &EventMetadata) -> Result<(), Error> {
         self.sender.send(metadata.clone().into()).await
     }
+
+    fn is_observed_table(&self, table_name: &str) -> bool {
+        self.mapper.is_mapped_table(table_name)
+    }
 }
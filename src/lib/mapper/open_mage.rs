@@ -0,0 +1,62 @@
+use crate::error::Error;
+use crate::log::ItemChange;
+use crate::mapper::builtin::TableMapper;
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Mapper for Magento 1 / OpenMage database
+///
+/// Media gallery changes are tracked only in `catalog_product_entity_media_gallery`,
+/// as store specific values in `catalog_product_entity_media_gallery_value` do not reference product.
+pub struct OpenMageMapper;
+
+impl OpenMageMapper {
+    fn table_mapper(table_name: &str) -> Option<TableMapper> {
+        Some(match table_name {
+            "catalog_product_entity_group_price" => TableMapper::ProductTierPrice,
+            "catalog_product_entity_media_gallery" => TableMapper::ProductMediaGallery,
+            "sales_flat_order"
+            | "sales_flat_order_item"
+            | "sales_flat_order_address"
+            | "sales_flat_order_status_history"
+            | "sales_flat_invoice"
+            | "sales_flat_shipment"
+            | "sales_flat_creditmemo"
+            | "sales_flat_quote"
+            | "sales_flat_quote_item"
+            | "sales_flat_quote_address" => TableMapper::Sales,
+            "core_store" | "core_store_group" | "core_website" => TableMapper::Store,
+            table_name => return TableMapper::shared(table_name),
+        })
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for OpenMageMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match Self::table_mapper(schema.table_name()) {
+            Some(mapper) => mapper.map_event_into(event, schema, changes),
+            None => Ok(()),
+        }
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        Self::table_mapper(table_name).is_some()
+    }
+}
@@ -0,0 +1,194 @@
+use crate::error::Error;
+use crate::log::{ItemChange, ProductChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::{ParentIndex, TableSchema};
+
+/// Propagates product changes to parents of composite products
+///
+/// Every product change produced by the mapper is followed by
+/// `ProductChange::ChildChanged` for each known parent of the changed product,
+/// so consumers that store children data on the parent can refresh it.
+/// Without parent index changes are passed as is.
+pub struct ParentPropagationMapper<M> {
+    mapper: M,
+    parents: Option<ParentIndex>,
+}
+
+impl<M> ParentPropagationMapper<M>
+where
+    M: ChangeLogMapper<ItemChange>,
+{
+    pub fn new(mapper: M, parents: Option<ParentIndex>) -> Self {
+        Self { mapper, parents }
+    }
+}
+
+impl<M> ChangeLogMapper<ItemChange> for ParentPropagationMapper<M>
+where
+    M: ChangeLogMapper<ItemChange>,
+{
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let parents = match &self.parents {
+            Some(parents) => parents,
+            None => return self.mapper.map_event_into(event, schema, changes),
+        };
+
+        let length = changes.len();
+        self.mapper.map_event_into(event, schema, changes)?;
+
+        let mut parent_ids: Vec<usize> = changes[length..]
+            .iter()
+            .filter_map(|change| match change {
+                ItemChange::ProductChange(ProductChange::ChildChanged(_)) => None,
+                ItemChange::ProductChange(change) => Some(parents.parents(change.entity_id())),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+
+        parent_ids.sort();
+        parent_ids.dedup();
+
+        changes.extend(
+            parent_ids
+                .into_iter()
+                .map(|parent_id| ItemChange::ProductChange(ProductChange::ChildChanged(parent_id))),
+        );
+
+        Ok(())
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        self.mapper.is_mapped_table(table_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::MagentoTwoMapper;
+    use crate::replication::UpdateRowEvent;
+    use crate::test_util::TestTableSchema;
+
+    const PRICE_TABLE: TestTableSchema = test_table!(
+        "catalog_product_entity_decimal",
+        ["value_id", "attribute_id", "store_id", "entity_id", "value"]
+    );
+
+    fn mapper(parents: Option<ParentIndex>) -> ParentPropagationMapper<MagentoTwoMapper> {
+        ParentPropagationMapper::new(MagentoTwoMapper, parents)
+    }
+
+    fn parents() -> ParentIndex {
+        let parents = ParentIndex::default();
+        parents
+            .apply_event(
+                &Event::InsertRow(binlog_row!(5, 10)),
+                &test_table!("catalog_product_relation", ["child_id", "parent_id"]),
+            )
+            .unwrap();
+        parents
+            .apply_event(
+                &Event::InsertRow(binlog_row!(1, 5, 20)),
+                &test_table!(
+                    "catalog_product_super_link",
+                    ["link_id", "product_id", "parent_id"]
+                ),
+            )
+            .unwrap();
+        parents
+    }
+
+    fn map_all(mapper: ParentPropagationMapper<MagentoTwoMapper>, event: Event) -> Vec<ItemChange> {
+        let mut changes = Vec::new();
+        mapper
+            .map_event_into(&event, &PRICE_TABLE, &mut changes)
+            .unwrap();
+        changes
+    }
+
+    #[test]
+    fn reports_parents_of_changed_child() {
+        assert_eq!(
+            map_all(
+                mapper(Some(parents())),
+                Event::InsertRow(binlog_row!(1, 77, 0, 5, "9.99"))
+            ),
+            vec![
+                ItemChange::ProductChange(ProductChange::Attribute(5, 77, 0)),
+                ItemChange::ProductChange(ProductChange::ChildChanged(10)),
+                ItemChange::ProductChange(ProductChange::ChildChanged(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_parents_of_child_going_out_of_stock() {
+        let mut changes = Vec::new();
+        mapper(Some(parents()))
+            .map_event_into(
+                &Event::UpdateRow(UpdateRowEvent::new(
+                    binlog_row!(1, 5, "1.0000", 1),
+                    binlog_row!(1, 5, "0.0000", 0),
+                )),
+                &test_table!(
+                    "cataloginventory_stock_item",
+                    ["item_id", "product_id", "qty", "is_in_stock"]
+                ),
+                &mut changes,
+            )
+            .unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                ItemChange::ProductChange(ProductChange::Key(5, "@stock")),
+                ItemChange::ProductChange(ProductChange::ChildChanged(10)),
+                ItemChange::ProductChange(ProductChange::ChildChanged(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_changes_of_products_without_parents() {
+        assert_eq!(
+            map_all(
+                mapper(Some(parents())),
+                Event::InsertRow(binlog_row!(1, 77, 0, 6, "9.99"))
+            ),
+            vec![ItemChange::ProductChange(ProductChange::Attribute(
+                6, 77, 0
+            ))]
+        );
+    }
+
+    #[test]
+    fn does_not_propagate_without_parent_index() {
+        assert_eq!(
+            map_all(
+                mapper(None),
+                Event::InsertRow(binlog_row!(1, 77, 0, 5, "9.99"))
+            ),
+            vec![ItemChange::ProductChange(ProductChange::Attribute(
+                5, 77, 0
+            ))]
+        );
+    }
+}
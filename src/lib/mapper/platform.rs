@@ -0,0 +1,115 @@
+use crate::error::Error;
+use crate::log::ItemChange;
+use crate::mapper::{ChangeLogMapper, MagentoTwoMapper, OpenMageMapper};
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+use serde::Deserialize;
+
+/// Database schema of the application changelog is generated for
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub enum Platform {
+    #[default]
+    #[serde(rename = "magento2", alias = "mage-os")]
+    MagentoTwo,
+    #[serde(rename = "openmage", alias = "magento1")]
+    OpenMage,
+}
+
+/// Built-in mapper for selected platform
+pub enum PlatformMapper {
+    MagentoTwo(MagentoTwoMapper),
+    OpenMage(OpenMageMapper),
+}
+
+impl From<Platform> for PlatformMapper {
+    fn from(platform: Platform) -> Self {
+        match platform {
+            Platform::MagentoTwo => Self::MagentoTwo(MagentoTwoMapper),
+            Platform::OpenMage => Self::OpenMage(OpenMageMapper),
+        }
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for PlatformMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        match self {
+            Self::MagentoTwo(mapper) => mapper.map_event(event, schema),
+            Self::OpenMage(mapper) => mapper.map_event(event, schema),
+        }
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match self {
+            Self::MagentoTwo(mapper) => mapper.map_event_into(event, schema, changes),
+            Self::OpenMage(mapper) => mapper.map_event_into(event, schema, changes),
+        }
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        match self {
+            Self::MagentoTwo(mapper) => mapper.is_mapped_table(table_name),
+            Self::OpenMage(mapper) => mapper.is_mapped_table(table_name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::ProductChange;
+
+    #[test]
+    fn maps_media_gallery_table_only_for_open_mage() {
+        for (platform, expected) in [
+            (Platform::MagentoTwo, None),
+            (
+                Platform::OpenMage,
+                Some(ItemChange::ProductChange(ProductChange::MediaGallery(4, 0))),
+            ),
+        ] {
+            mapper_test!(
+                PlatformMapper::from(platform),
+                expected,
+                Event::InsertRow(binlog_row!(1, 88, 4, "/i/m/image.jpg")),
+                test_table!(
+                    "catalog_product_entity_media_gallery",
+                    ["value_id", "attribute_id", "entity_id", "value"]
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn maps_group_price_table_of_open_mage_as_tier_price() {
+        mapper_test!(
+            PlatformMapper::from(Platform::OpenMage),
+            Some(ItemChange::ProductChange(ProductChange::TierPrice(
+                4,
+                0,
+                Some(1)
+            ))),
+            Event::InsertRow(binlog_row!(1, 4, 0, 1, "10.00", 0)),
+            test_table!(
+                "catalog_product_entity_group_price",
+                [
+                    "value_id",
+                    "entity_id",
+                    "all_groups",
+                    "customer_group_id",
+                    "value",
+                    "website_id"
+                ]
+            )
+        );
+    }
+}
@@ -24,6 +24,26 @@ impl ChangeLogMapper<ProductChange> for ProductMapper {
                 .into_change_log(),
         })
     }
+
+    /// Reports update of `entity_id` as deleted product and created product with new id
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        match event {
+            Event::UpdateRow(row) if row.is_changed_column("entity_id", schema) => {
+                changes.push(ProductChange::Deleted(row.parse("entity_id", schema)?));
+                changes.push(ProductChange::Created(
+                    row.parse_changed("entity_id", schema)?,
+                ));
+            }
+            event => changes.extend(self.map_event(event, schema)?),
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +80,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn maps_update_of_entity_id_as_deleted_and_created_product() {
+        mapper_changes_test!(
+            ProductMapper,
+            vec![ProductChange::Deleted(3), ProductChange::Created(4)],
+            update[("SKU2", 3), ("SKU2", 4)],
+            ["sku", "entity_id"]
+        );
+    }
+
     #[test]
     fn maps_updated_row_for_fields_that_matter() {
         mapper_test!(
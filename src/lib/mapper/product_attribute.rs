@@ -12,22 +12,37 @@ impl ChangeLogMapper<ProductChange> for ProductAttributeMapper {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::InsertRow(row) | Event::DeleteRow(row) => Some(ProductChange::Attribute(
-                row.parse("entity_id", schema)?,
-                row.parse("attribute_id", schema)?,
-            )),
-            Event::UpdateRow(row)
-                if row.is_changed_column("store_id", schema)
-                    || row.is_changed_column("value", schema) =>
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("entity_id", schema)
+                || row.is_changed_column("attribute_id", schema)
+                || row.is_changed_column("store_id", schema)
+                || row.is_changed_column("value", schema))
             {
-                Some(ProductChange::Attribute(
-                    row.parse("entity_id", schema)?,
-                    row.parse("attribute_id", schema)?,
-                ))
+                return Ok(());
             }
-            _ => None,
-        })
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::Attribute(
+                row.parse("entity_id", schema)?,
+                row.parse("attribute_id", schema)?,
+                row.parse("store_id", schema)?,
+            ))
+        })?);
+
+        Ok(())
     }
 }
 
@@ -39,7 +54,7 @@ mod tests {
     fn maps_insert_row_event_as_update_of_attribute() {
         mapper_test!(
             ProductAttributeMapper,
-            Some(ProductChange::Attribute(1, 2)),
+            Some(ProductChange::Attribute(1, 2, 3)),
             insert[1, 2, 3, "test"],
             ["entity_id", "attribute_id", "store_id", "value"]
         );
@@ -49,7 +64,7 @@ mod tests {
     fn maps_delete_row_event_as_update_of_attribute() {
         mapper_test!(
             ProductAttributeMapper,
-            Some(ProductChange::Attribute(2, 4)),
+            Some(ProductChange::Attribute(2, 4, 3)),
             delete[2, 4, 3, "test"],
             ["entity_id", "attribute_id", "store_id", "value"]
         );
@@ -69,19 +84,35 @@ mod tests {
     fn maps_update_event_to_product_attribute_update_if_value_changed() {
         mapper_test!(
             ProductAttributeMapper,
-            Some(ProductChange::Attribute(2, 4)),
+            Some(ProductChange::Attribute(2, 4, 1)),
             update[(2, 4, 1, "test"), (2, 4, 1, "test2")],
             ["entity_id", "attribute_id", "store_id", "value"]
         );
     }
 
     #[test]
-    fn maps_update_event_to_product_attribute_update_if_store_id_changed() {
-        mapper_test!(
+    fn maps_update_event_to_product_attribute_update_for_both_stores_if_store_id_changed() {
+        mapper_changes_test!(
             ProductAttributeMapper,
-            Some(ProductChange::Attribute(2, 4)),
+            vec![
+                ProductChange::Attribute(2, 4, 1),
+                ProductChange::Attribute(2, 4, 3)
+            ],
             update[(2, 4, 1, "test"), (2, 4, 3, "test")],
             ["entity_id", "attribute_id", "store_id", "value"]
         );
     }
+
+    #[test]
+    fn maps_update_event_moving_value_to_another_product_for_both_products() {
+        mapper_changes_test!(
+            ProductAttributeMapper,
+            vec![
+                ProductChange::Attribute(2, 4, 1),
+                ProductChange::Attribute(3, 4, 1)
+            ],
+            update[(2, 4, 1, "test"), (3, 4, 1, "test")],
+            ["entity_id", "attribute_id", "store_id", "value"]
+        );
+    }
 }
@@ -12,22 +12,37 @@ impl ChangeLogMapper<ProductChange> for ProductBundleMapper {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::InsertRow(row) | Event::DeleteRow(row) => Some(
-                ProductChange::CompositeRelation(row.parse("parent_product_id", schema)?),
-            ),
-            Event::UpdateRow(row)
-                if row.is_changed_column("is_default", schema)
-                    | row.is_changed_column("selection_price_type", schema)
-                    | row.is_changed_column("selection_price_value", schema)
-                    | row.is_changed_column("selection_qty", schema) =>
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("parent_product_id", schema)
+                || row.is_changed_column("product_id", schema)
+                || row.is_changed_column("is_default", schema)
+                || row.is_changed_column("selection_price_type", schema)
+                || row.is_changed_column("selection_price_value", schema)
+                || row.is_changed_column("selection_qty", schema))
             {
-                Some(ProductChange::CompositeRelation(
-                    row.parse("parent_product_id", schema)?,
-                ))
+                return Ok(());
             }
-            _ => None,
-        })
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::CompositeRelation(
+                row.parse("parent_product_id", schema)?,
+            ))
+        })?);
+
+        Ok(())
     }
 }
 
@@ -104,6 +119,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn maps_update_moving_selection_to_another_bundle_for_both_bundles() {
+        mapper_changes_test!(
+            ProductBundleMapper,
+            vec![
+                ProductChange::CompositeRelation(3),
+                ProductChange::CompositeRelation(5)
+            ],
+            update[(3, 1), (5, 1)],
+            ["parent_product_id", "is_default"]
+        );
+    }
+
     #[test]
     fn maps_update_of_unrelated_property_into_none() {
         mapper_test!(
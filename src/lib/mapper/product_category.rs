@@ -12,13 +12,41 @@ impl ChangeLogMapper<ProductChange> for ProductCategoryMapper {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::DeleteRow(row) | Event::InsertRow(row) => Some(ProductChange::Category(
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("product_id", schema)
+                || row.is_changed_column("category_id", schema))
+            {
+                if row.is_changed_column("position", schema) {
+                    changes.push(ProductChange::CategoryPosition(
+                        row.parse("product_id", schema)?,
+                        row.parse("category_id", schema)?,
+                    ));
+                }
+
+                return Ok(());
+            }
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::Category(
                 row.parse("product_id", schema)?,
                 row.parse("category_id", schema)?,
-            )),
-            _ => None,
-        })
+            ))
+        })?);
+
+        Ok(())
     }
 }
 
@@ -48,12 +76,32 @@ mod tests {
     }
 
     #[test]
-    fn ignores_update_event_as_it_has_no_meaning() {
+    fn maps_update_moving_product_to_another_category_for_both_categories() {
+        mapper_changes_test!(
+            ProductCategoryMapper,
+            vec![ProductChange::Category(2, 4), ProductChange::Category(2, 5)],
+            update[(2, 4), (2, 5)],
+            ["product_id", "category_id"]
+        );
+    }
+
+    #[test]
+    fn maps_position_update_into_category_position_change() {
+        mapper_test!(
+            ProductCategoryMapper,
+            Some(ProductChange::CategoryPosition(2, 4)),
+            update[(2, 4, 1), (2, 4, 5)],
+            ["product_id", "category_id", "position"]
+        );
+    }
+
+    #[test]
+    fn ignores_update_event_without_assignment_or_position_change() {
         mapper_test!(
             ProductCategoryMapper,
             None,
-            update[(2, 4), (2, 4)],
-            ["product_id", "category_id"]
+            update[(2, 4, 1), (2, 4, 1)],
+            ["product_id", "category_id", "position"]
         );
     }
 }
@@ -12,14 +12,36 @@ impl ChangeLogMapper<ProductChange> for ProductConfigurableMapper {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::InsertRow(row) | Event::DeleteRow(row) => Some(
-                ProductChange::CompositeRelation(row.parse("parent_id", schema)?),
-            ),
-            _ => None,
-        })
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("parent_id", schema)
+                || row.is_changed_column("product_id", schema))
+            {
+                return Ok(());
+            }
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::CompositeRelation(
+                row.parse("parent_id", schema)?,
+            ))
+        })?);
+
+        Ok(())
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,12 +64,25 @@ mod tests {
     }
 
     #[test]
-    fn ignores_update_event_as_change() {
-        mapper_test!(
+    fn maps_update_moving_child_to_another_parent_for_both_parents() {
+        mapper_changes_test!(
             ProductConfigurableMapper,
-            None,
+            vec![
+                ProductChange::CompositeRelation(1),
+                ProductChange::CompositeRelation(2)
+            ],
             update[(1, 2), (2, 3)],
             ["parent_id", "product_id"]
         );
     }
+
+    #[test]
+    fn ignores_update_event_without_link_change() {
+        mapper_test!(
+            ProductConfigurableMapper,
+            None,
+            update[(1, 1, 2), (2, 1, 2)],
+            ["link_id", "parent_id", "product_id"]
+        );
+    }
 }
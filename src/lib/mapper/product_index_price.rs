@@ -0,0 +1,63 @@
+use crate::error::Error;
+use crate::log::ProductChange;
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Mapper for indexed product prices scoped by website and customer group
+pub struct ProductIndexPriceMapper;
+
+impl ChangeLogMapper<ProductChange> for ProductIndexPriceMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ProductChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::IndexPrice(
+                row.parse("entity_id", schema)?,
+                row.parse("website_id", schema)?,
+                row.parse("customer_group_id", schema)?,
+            ))
+        })?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_insert_event_into_index_price_change() {
+        mapper_test!(
+            ProductIndexPriceMapper,
+            Some(ProductChange::IndexPrice(2, 1, 0)),
+            insert[2, 0, 1, "10.00"],
+            ["entity_id", "customer_group_id", "website_id", "final_price"]
+        );
+    }
+
+    #[test]
+    fn maps_update_event_into_index_price_change() {
+        mapper_test!(
+            ProductIndexPriceMapper,
+            Some(ProductChange::IndexPrice(2, 1, 3)),
+            update[(2, 3, 1, "10.00"), (2, 3, 1, "8.00")],
+            ["entity_id", "customer_group_id", "website_id", "final_price"]
+        );
+    }
+}
@@ -12,16 +12,26 @@ impl ChangeLogMapper<ProductChange> for ProductLinkMapper {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::InsertRow(row) | Event::DeleteRow(row) => Some(ProductChange::LinkRelation(
-                row.parse("product_id", schema)?,
-                row.parse("link_type_id", schema)?,
-            )),
-            Event::UpdateRow(row) => Some(ProductChange::LinkRelation(
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::LinkRelation(
                 row.parse("product_id", schema)?,
                 row.parse("link_type_id", schema)?,
-            )),
-        })
+            ))
+        })?);
+
+        Ok(())
     }
 }
 
@@ -55,4 +65,17 @@ mod tests {
             ["product_id", "linked_product_id", "link_type_id"]
         );
     }
+
+    #[test]
+    fn maps_update_moving_link_to_another_product_for_both_products() {
+        mapper_changes_test!(
+            ProductLinkMapper,
+            vec![
+                ProductChange::LinkRelation(1, 3),
+                ProductChange::LinkRelation(2, 3)
+            ],
+            update[(1, 1, 3), (2, 1, 3)],
+            ["product_id", "linked_product_id", "link_type_id"]
+        );
+    }
 }
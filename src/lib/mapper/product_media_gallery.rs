@@ -0,0 +1,82 @@
+use crate::error::Error;
+use crate::log::ProductChange;
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Mapper for gallery table with `entity_id` column as it is in Magento 1 / OpenMage
+///
+/// Gallery images are global, so changes are reported for admin store.
+pub struct ProductMediaGalleryMapper;
+
+impl ChangeLogMapper<ProductChange> for ProductMediaGalleryMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ProductChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("entity_id", schema)
+                || row.is_changed_column("value", schema))
+            {
+                return Ok(());
+            }
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::MediaGallery(
+                row.parse("entity_id", schema)?,
+                0,
+            ))
+        })?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_insert_event_into_media_gallery_event() {
+        mapper_test!(
+            ProductMediaGalleryMapper,
+            Some(ProductChange::MediaGallery(4, 0)),
+            insert[1, 88, 4, "/i/m/image.jpg"],
+            ["value_id", "attribute_id", "entity_id", "value"]
+        );
+    }
+
+    #[test]
+    fn maps_update_of_image_path_into_media_gallery_event() {
+        mapper_test!(
+            ProductMediaGalleryMapper,
+            Some(ProductChange::MediaGallery(4, 0)),
+            update[(1, 88, 4, "/i/m/image.jpg"), (1, 88, 4, "/i/m/image_1.jpg")],
+            ["value_id", "attribute_id", "entity_id", "value"]
+        );
+    }
+
+    #[test]
+    fn ignores_update_without_image_path_change() {
+        mapper_test!(
+            ProductMediaGalleryMapper,
+            None,
+            update[(1, 88, 4, "/i/m/image.jpg"), (1, 89, 4, "/i/m/image.jpg")],
+            ["value_id", "attribute_id", "entity_id", "value"]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use crate::error::Error;
+use crate::log::{ItemChange, ProductChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::{MediaGalleryIndex, TableSchema};
+
+/// Mapper for gallery images and their links to products in Magento 2
+///
+/// Replaced image file, media type or disabled flag of an image is reported
+/// for every product the image is linked to, resolved through maintained index.
+/// Linking an image to a product is reported for the linked product,
+/// resolved from its row version in Adobe Commerce.
+/// Both are global, so changes are reported for admin store.
+pub struct ProductMediaGalleryEntityMapper {
+    products: MediaGalleryIndex,
+}
+
+impl ProductMediaGalleryEntityMapper {
+    pub fn new(products: MediaGalleryIndex) -> Self {
+        Self { products }
+    }
+
+    fn map_image(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("value", schema)
+                || row.is_changed_column("media_type", schema)
+                || row.is_changed_column("disabled", schema))
+            {
+                return Ok(());
+            }
+        }
+
+        for value_id in event.map_owners(|row| row.parse("value_id", schema))? {
+            changes.extend(
+                self.products
+                    .products(value_id)
+                    .into_iter()
+                    .map(|entity_id| {
+                        ItemChange::ProductChange(ProductChange::MediaGallery(entity_id, 0))
+                    }),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn map_link(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let column = match ["entity_id", "row_id"]
+            .into_iter()
+            .find(|column| schema.has_column(column))
+        {
+            Some(column) => column,
+            None => return Ok(()),
+        };
+
+        for linked_id in event.map_owners(|row| row.parse(column, schema))? {
+            changes.extend(self.products.product(linked_id).map(|entity_id| {
+                ItemChange::ProductChange(ProductChange::MediaGallery(entity_id, 0))
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for ProductMediaGalleryEntityMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match schema.table_name() {
+            // Gallery of Magento 1 / OpenMage references product directly
+            "catalog_product_entity_media_gallery" if !schema.has_column("entity_id") => {
+                self.map_image(event, schema, changes)
+            }
+            "catalog_product_entity_media_gallery_value_to_entity" => {
+                self.map_link(event, schema, changes)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        matches!(
+            table_name,
+            "catalog_product_entity_media_gallery"
+                | "catalog_product_entity_media_gallery_value_to_entity"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+    use crate::schema::RowIdResolver;
+
+    fn mapper() -> ProductMediaGalleryEntityMapper {
+        let products = MediaGalleryIndex::default();
+        products.insert(1, 10);
+        products.insert(1, 11);
+        products.insert(2, 12);
+
+        ProductMediaGalleryEntityMapper::new(products)
+    }
+
+    fn gallery_change(entity_id: usize) -> ItemChange {
+        ItemChange::ProductChange(ProductChange::MediaGallery(entity_id, 0))
+    }
+
+    #[test]
+    fn maps_replaced_image_file_for_all_linked_products() {
+        mapper_changes_test!(
+            mapper(),
+            vec![gallery_change(10), gallery_change(11)],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 90, "/i/m/image.jpg", "image", 0),
+                binlog_row!(1, 90, "/i/m/image_1.jpg", "image", 0)
+            )),
+            test_table!(
+                "catalog_product_entity_media_gallery",
+                [
+                    "value_id",
+                    "attribute_id",
+                    "value",
+                    "media_type",
+                    "disabled"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_image_update_without_file_change() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 90, "/i/m/image.jpg", "image", 0),
+                binlog_row!(1, 88, "/i/m/image.jpg", "image", 0)
+            )),
+            test_table!(
+                "catalog_product_entity_media_gallery",
+                [
+                    "value_id",
+                    "attribute_id",
+                    "value",
+                    "media_type",
+                    "disabled"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_gallery_table_of_openmage() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::DeleteRow(binlog_row!(1, 88, 4, "/i/m/image.jpg")),
+            test_table!(
+                "catalog_product_entity_media_gallery",
+                ["value_id", "attribute_id", "entity_id", "value"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_image_of_product_row_version_with_content_staging() {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert(100, 10);
+        row_ids.insert(101, 10);
+        row_ids.set_content_staging();
+
+        let products = MediaGalleryIndex::default().with_row_id_resolver(row_ids);
+        products.insert(1, 100);
+        products.insert(1, 101);
+        let mapper = ProductMediaGalleryEntityMapper::new(products);
+
+        mapper_changes_test!(
+            mapper,
+            vec![gallery_change(10)],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 90, "/i/m/image.jpg", "image", 0),
+                binlog_row!(1, 90, "/i/m/image_1.jpg", "image", 0)
+            )),
+            test_table!(
+                "catalog_product_entity_media_gallery",
+                [
+                    "value_id",
+                    "attribute_id",
+                    "value",
+                    "media_type",
+                    "disabled"
+                ]
+            )
+        );
+
+        mapper_test!(
+            mapper,
+            Some(gallery_change(10)),
+            Event::InsertRow(binlog_row!(2, 101)),
+            test_table!(
+                "catalog_product_entity_media_gallery_value_to_entity",
+                ["value_id", "row_id"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_existing_image_linked_to_product() {
+        mapper_test!(
+            mapper(),
+            Some(gallery_change(20)),
+            Event::InsertRow(binlog_row!(1, 20)),
+            test_table!(
+                "catalog_product_entity_media_gallery_value_to_entity",
+                ["value_id", "entity_id"]
+            )
+        );
+    }
+}
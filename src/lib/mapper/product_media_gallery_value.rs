@@ -4,6 +4,7 @@ use crate::mapper::ChangeLogMapper;
 use crate::replication::Event;
 use crate::schema::TableSchema;
 
+/// Mapper for store specific labels and visibility of gallery images, scoped by store
 pub struct ProductMediaGalleryValue;
 
 impl ChangeLogMapper<ProductChange> for ProductMediaGalleryValue {
@@ -12,19 +13,36 @@ impl ChangeLogMapper<ProductChange> for ProductMediaGalleryValue {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::InsertRow(row) | Event::DeleteRow(row) => {
-                Some(ProductChange::MediaGallery(row.parse("entity_id", schema)?))
-            }
-            Event::UpdateRow(row)
-                if row.is_changed_column("store_id", schema)
-                    | row.is_changed_column("label", schema)
-                    | row.is_changed_column("disabled", schema) =>
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("entity_id", schema)
+                || row.is_changed_column("store_id", schema)
+                || row.is_changed_column("label", schema)
+                || row.is_changed_column("disabled", schema))
             {
-                Some(ProductChange::MediaGallery(row.parse("entity_id", schema)?))
+                return Ok(());
             }
-            _ => None,
-        })
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::MediaGallery(
+                row.parse("entity_id", schema)?,
+                row.parse("store_id", schema)?,
+            ))
+        })?);
+
+        Ok(())
     }
 }
 
@@ -36,7 +54,7 @@ mod tests {
     fn maps_insert_event_into_media_gallery_event() {
         mapper_test!(
             ProductMediaGalleryValue,
-            Some(ProductChange::MediaGallery(4)),
+            Some(ProductChange::MediaGallery(4, 2)),
             insert[2, 4, 3],
             ["store_id", "entity_id", "value_id"]
         );
@@ -46,7 +64,7 @@ mod tests {
     fn maps_delete_event_into_media_gallery_event() {
         mapper_test!(
             ProductMediaGalleryValue,
-            Some(ProductChange::MediaGallery(5)),
+            Some(ProductChange::MediaGallery(5, 2)),
             delete[2, 5, 3],
             ["store_id", "entity_id", "value_id"]
         );
@@ -54,22 +72,34 @@ mod tests {
 
     #[test]
     fn maps_update_event_into_media_gallery_event_when_store_is_updated() {
-        mapper_test!(
+        mapper_changes_test!(
             ProductMediaGalleryValue,
-
-            Some(ProductChange::MediaGallery(2)),
+            vec![ProductChange::MediaGallery(2, 1), ProductChange::MediaGallery(2, 0)],
             update[(2, 1), (2, 0)],
             ["entity_id", "store_id"]
         );
     }
 
+    #[test]
+    fn maps_update_event_moving_value_to_another_product_for_both_products() {
+        mapper_changes_test!(
+            ProductMediaGalleryValue,
+            vec![
+                ProductChange::MediaGallery(2, 1),
+                ProductChange::MediaGallery(3, 1)
+            ],
+            update[(2, 1), (3, 1)],
+            ["entity_id", "store_id"]
+        );
+    }
+
     #[test]
     fn maps_update_event_into_none_when_not_related_field_is_updated() {
         mapper_test!(
             ProductMediaGalleryValue,
             None,
-            update[(2, 1), (2, 0)],
-            ["entity_id", "position"]
+            update[(2, 1, 1), (2, 1, 0)],
+            ["entity_id", "store_id", "position"]
         );
     }
 
@@ -77,9 +107,9 @@ mod tests {
     fn maps_update_event_into_media_gallery_event_when_label_is_updated() {
         mapper_test!(
             ProductMediaGalleryValue,
-            Some(ProductChange::MediaGallery(2)),
-            update[(2, ""), (2, "Changed")],
-            ["entity_id", "label"]
+            Some(ProductChange::MediaGallery(2, 0)),
+            update[(2, 0, ""), (2, 0, "Changed")],
+            ["entity_id", "store_id", "label"]
         );
     }
 
@@ -87,9 +117,9 @@ mod tests {
     fn maps_update_event_into_media_gallery_event_when_disable_is_updated() {
         mapper_test!(
             ProductMediaGalleryValue,
-            Some(ProductChange::MediaGallery(7)),
-            update[(7, 0), (7, 1)],
-            ["entity_id", "disabled"]
+            Some(ProductChange::MediaGallery(7, 1)),
+            update[(7, 1, 0), (7, 1, 1)],
+            ["entity_id", "store_id", "disabled"]
         );
     }
 }
@@ -0,0 +1,255 @@
+use crate::error::Error;
+use crate::log::{ItemChange, ProductChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::{
+    Relation, RelationIndex, TableSchema, PRODUCT_OPTION, PRODUCT_OPTION_TYPE,
+    PRODUCT_SUPER_ATTRIBUTE,
+};
+use smallvec::SmallVec;
+
+/// Products before and after update of the row
+type ProductIds = SmallVec<[usize; 2]>;
+
+/// Tables of labels, titles and prices with column referencing their option or super attribute
+static RELATED_TABLES: [(&str, &str, &Relation); 7] = [
+    ("catalog_product_option_title", "option_id", &PRODUCT_OPTION),
+    ("catalog_product_option_price", "option_id", &PRODUCT_OPTION),
+    (
+        "catalog_product_option_type_value",
+        "option_id",
+        &PRODUCT_OPTION,
+    ),
+    (
+        "catalog_product_option_type_title",
+        "option_type_id",
+        &PRODUCT_OPTION_TYPE,
+    ),
+    (
+        "catalog_product_option_type_price",
+        "option_type_id",
+        &PRODUCT_OPTION_TYPE,
+    ),
+    (
+        "catalog_product_super_attribute_label",
+        "product_super_attribute_id",
+        &PRODUCT_SUPER_ATTRIBUTE,
+    ),
+    (
+        "catalog_product_super_attribute_pricing",
+        "product_super_attribute_id",
+        &PRODUCT_SUPER_ATTRIBUTE,
+    ),
+];
+
+/// Mapper for configurable super attributes and custom options
+///
+/// Rows of labels, titles and prices are reported for the product
+/// resolved through super attribute or option they belong to.
+pub struct ProductRelationMapper {
+    relations: RelationIndex,
+}
+
+impl ProductRelationMapper {
+    pub fn new(relations: RelationIndex) -> Self {
+        for (table_name, column, relation) in RELATED_TABLES {
+            relations.register(table_name, column, relation);
+        }
+
+        Self { relations }
+    }
+
+    fn related_table(table_name: &str) -> Option<(&'static str, &'static Relation)> {
+        RELATED_TABLES
+            .into_iter()
+            .find(|(table, _, _)| *table == table_name)
+            .map(|(_, column, relation)| (column, relation))
+    }
+
+    fn map_ids(
+        event: &Event,
+        schema: &impl TableSchema,
+        column: &str,
+    ) -> Result<ProductIds, Error> {
+        event.map_owners(|row| row.parse(column, schema))
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for ProductRelationMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let (products, change): (ProductIds, fn(usize) -> ProductChange) = match schema.table_name()
+        {
+            "catalog_product_option" => (
+                Self::map_ids(event, schema, "product_id")?,
+                ProductChange::Options,
+            ),
+            "catalog_product_super_attribute" => (
+                Self::map_ids(event, schema, "product_id")?,
+                ProductChange::SuperAttribute,
+            ),
+            table_name => {
+                let (column, relation) = match Self::related_table(table_name) {
+                    Some(relation) => relation,
+                    None => return Ok(()),
+                };
+
+                let mut products: ProductIds = Self::map_ids(event, schema, column)?
+                    .into_iter()
+                    .filter_map(|id| self.relations.resolve(relation, id))
+                    .collect();
+                products.dedup();
+
+                if relation == &PRODUCT_SUPER_ATTRIBUTE {
+                    (products, ProductChange::SuperAttribute)
+                } else {
+                    (products, ProductChange::Options)
+                }
+            }
+        };
+
+        changes.extend(
+            products
+                .into_iter()
+                .map(|product_id| ItemChange::ProductChange(change(product_id))),
+        );
+
+        Ok(())
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        match table_name {
+            "catalog_product_option" | "catalog_product_super_attribute" => true,
+            table_name => Self::related_table(table_name).is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn mapper() -> ProductRelationMapper {
+        let relations = RelationIndex::default();
+        relations.insert(&PRODUCT_OPTION, 3, 10);
+        relations.insert(&PRODUCT_OPTION_TYPE, 7, 3);
+        relations.insert(&PRODUCT_SUPER_ATTRIBUTE, 5, 20);
+
+        ProductRelationMapper::new(relations)
+    }
+
+    #[test]
+    fn maps_option_into_options_change() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::Options(10))),
+            Event::InsertRow(binlog_row!(4, 10, "field")),
+            test_table!(
+                "catalog_product_option",
+                ["option_id", "product_id", "type"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_option_title_through_option() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::Options(10))),
+            Event::InsertRow(binlog_row!(1, 3, 0, "Engraving")),
+            test_table!(
+                "catalog_product_option_title",
+                ["option_title_id", "option_id", "store_id", "title"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_option_value_price_through_option_value_and_option() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::Options(10))),
+            Event::DeleteRow(binlog_row!(1, 7, 0, "5.00", "fixed")),
+            test_table!(
+                "catalog_product_option_type_price",
+                [
+                    "option_type_price_id",
+                    "option_type_id",
+                    "store_id",
+                    "price",
+                    "price_type"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_super_attribute_label_through_super_attribute() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::SuperAttribute(20))),
+            Event::InsertRow(binlog_row!(1, 5, 0, 1, "Color")),
+            test_table!(
+                "catalog_product_super_attribute_label",
+                [
+                    "value_id",
+                    "product_super_attribute_id",
+                    "store_id",
+                    "use_default",
+                    "value"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_option_title_moved_to_another_option_for_both_products() {
+        let mapper = mapper();
+        mapper.relations.insert(&PRODUCT_OPTION, 4, 11);
+
+        mapper_changes_test!(
+            mapper,
+            vec![
+                ItemChange::ProductChange(ProductChange::Options(10)),
+                ItemChange::ProductChange(ProductChange::Options(11))
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 3, 0, "Engraving"),
+                binlog_row!(1, 4, 0, "Engraving")
+            )),
+            test_table!(
+                "catalog_product_option_title",
+                ["option_title_id", "option_id", "store_id", "title"]
+            )
+        );
+    }
+
+    #[test]
+    fn skips_rows_of_unknown_option() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(1, 4, 0, "Engraving")),
+            test_table!(
+                "catalog_product_option_title",
+                ["option_title_id", "option_id", "store_id", "title"]
+            )
+        );
+    }
+}
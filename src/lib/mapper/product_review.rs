@@ -0,0 +1,315 @@
+use crate::error::Error;
+use crate::log::{ItemChange, ProductChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::{Event, EventRow};
+use crate::schema::{Relation, RelationIndex, TableSchema};
+
+/// Id of product in `review_entity` table
+const PRODUCT_REVIEW_ENTITY: usize = 1;
+
+const REVIEW_KEY: &str = "@review";
+
+/// Relation of product reviews to the reviewed product
+static PRODUCT_REVIEW: Relation = Relation {
+    table: "review",
+    id: "review_id",
+    owner: "entity_pk_value",
+    parent: None,
+    condition: Some(("entity_id", PRODUCT_REVIEW_ENTITY)),
+};
+
+/// Mapper for product reviews, rating votes and review summaries
+///
+/// Changes are reported under `@review` key for the reviewed product,
+/// scoped by store for store specific review details, visibility of review in store
+/// and rating summaries. Reviews of other entities than product are skipped.
+/// Review details and stores are resolved to product through review relation
+/// the mapper registers in `schema::RelationIndex`.
+pub struct ProductReviewMapper {
+    reviews: RelationIndex,
+}
+
+impl ProductReviewMapper {
+    pub fn new(reviews: RelationIndex) -> Self {
+        for table_name in ["review_detail", "review_store"] {
+            reviews.register(table_name, "review_id", &PRODUCT_REVIEW);
+        }
+
+        Self { reviews }
+    }
+
+    fn product_id(
+        row: &EventRow,
+        schema: &impl TableSchema,
+        entity_type: &str,
+    ) -> Result<Option<usize>, Error> {
+        if row.parse::<usize>(entity_type, schema)? != PRODUCT_REVIEW_ENTITY {
+            return Ok(None);
+        }
+
+        Ok(Some(row.parse("entity_pk_value", schema)?))
+    }
+
+    fn map_review(
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        changes.extend(
+            event
+                .map_owners(|row| Self::product_id(&row, schema, "entity_id"))?
+                .into_iter()
+                .flatten()
+                .map(|product_id| {
+                    ItemChange::ProductChange(ProductChange::Key(product_id, REVIEW_KEY))
+                }),
+        );
+
+        Ok(())
+    }
+
+    fn map_vote(
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        changes.extend(
+            event
+                .map_owners(|row| {
+                    Ok(ProductChange::Key(
+                        row.parse("entity_pk_value", schema)?,
+                        REVIEW_KEY,
+                    ))
+                })?
+                .into_iter()
+                .map(ItemChange::ProductChange),
+        );
+
+        Ok(())
+    }
+
+    fn map_summary(
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let owners = event.map_owners(|row| {
+            let store_id = row.parse("store_id", schema)?;
+
+            Ok(Self::product_id(&row, schema, "entity_type")?
+                .map(|product_id| ProductChange::ScopedKey(product_id, REVIEW_KEY, store_id)))
+        })?;
+
+        changes.extend(owners.into_iter().flatten().map(ItemChange::ProductChange));
+
+        Ok(())
+    }
+
+    fn map_review_store(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let owners = event.map_owners(|row| {
+            Ok((
+                row.parse("review_id", schema)?,
+                row.parse("store_id", schema)?,
+            ))
+        })?;
+
+        changes.extend(owners.into_iter().filter_map(|(review_id, store_id)| {
+            self.reviews
+                .resolve(&PRODUCT_REVIEW, review_id)
+                .map(|product_id| {
+                    ItemChange::ProductChange(ProductChange::ScopedKey(
+                        product_id, REVIEW_KEY, store_id,
+                    ))
+                })
+        }));
+
+        Ok(())
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for ProductReviewMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match schema.table_name() {
+            "review" => Self::map_review(event, schema, changes),
+            "review_detail" | "review_store" => self.map_review_store(event, schema, changes),
+            "rating_option_vote" => Self::map_vote(event, schema, changes),
+            "review_entity_summary" => Self::map_summary(event, schema, changes),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        matches!(
+            table_name,
+            "review"
+                | "review_detail"
+                | "review_store"
+                | "rating_option_vote"
+                | "review_entity_summary"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn mapper() -> ProductReviewMapper {
+        let reviews = RelationIndex::default();
+        reviews.insert(&PRODUCT_REVIEW, 5, 10);
+
+        ProductReviewMapper::new(reviews)
+    }
+
+    fn review_change(product_id: usize) -> ItemChange {
+        ItemChange::ProductChange(ProductChange::Key(product_id, "@review"))
+    }
+
+    fn scoped_review_change(product_id: usize, store_id: usize) -> ItemChange {
+        ItemChange::ProductChange(ProductChange::ScopedKey(product_id, "@review", store_id))
+    }
+
+    #[test]
+    fn maps_approved_review_of_product() {
+        mapper_test!(
+            mapper(),
+            Some(review_change(10)),
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(5, 1, 10, 2),
+                binlog_row!(5, 1, 10, 1)
+            )),
+            test_table!(
+                "review",
+                ["review_id", "entity_id", "entity_pk_value", "status_id"]
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_review_of_other_entities() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(6, 3, 4, 2)),
+            test_table!(
+                "review",
+                ["review_id", "entity_id", "entity_pk_value", "status_id"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_review_store_through_review_scoped_by_store() {
+        mapper_test!(
+            mapper(),
+            Some(scoped_review_change(10, 1)),
+            Event::InsertRow(binlog_row!(5, 1)),
+            test_table!("review_store", ["review_id", "store_id"])
+        );
+    }
+
+    #[test]
+    fn skips_detail_of_unknown_review() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(1, 6, 1, "Great")),
+            test_table!(
+                "review_detail",
+                ["detail_id", "review_id", "store_id", "title"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_rating_vote_of_product() {
+        mapper_test!(
+            mapper(),
+            Some(review_change(10)),
+            Event::InsertRow(binlog_row!(1, 4, 10, 5, 5)),
+            test_table!(
+                "rating_option_vote",
+                [
+                    "vote_id",
+                    "option_id",
+                    "entity_pk_value",
+                    "review_id",
+                    "value"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_review_summary_scoped_by_store() {
+        mapper_test!(
+            mapper(),
+            Some(scoped_review_change(10, 2)),
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 10, 1, 3, 80, 2),
+                binlog_row!(1, 10, 1, 4, 85, 2)
+            )),
+            test_table!(
+                "review_entity_summary",
+                [
+                    "primary_id",
+                    "entity_pk_value",
+                    "entity_type",
+                    "reviews_count",
+                    "rating_summary",
+                    "store_id"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn registers_only_reviews_of_products() {
+        let index = RelationIndex::default();
+        ProductReviewMapper::new(index.clone());
+        let schema = test_table!(
+            "review",
+            ["review_id", "entity_id", "entity_pk_value", "status_id"]
+        );
+
+        index
+            .apply_event(&Event::InsertRow(binlog_row!(5, 1, 10, 1)), &schema)
+            .unwrap();
+        index
+            .apply_event(&Event::InsertRow(binlog_row!(6, 3, 20, 1)), &schema)
+            .unwrap();
+
+        assert_eq!(
+            (
+                vec![
+                    index.resolve(&PRODUCT_REVIEW, 5),
+                    index.resolve(&PRODUCT_REVIEW, 6)
+                ],
+                index.table_relation("review_store"),
+            ),
+            (vec![Some(10), None], Some(("review_id", &PRODUCT_REVIEW)))
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::error::Error;
+use crate::log::ProductChange;
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Key of stock item changes in product aggregate
+const STOCK_KEY: &str = "@stock";
+
+/// Mapper for product stock items
+///
+/// Changes of quantity, stock status, stock management and backorders
+/// in `cataloginventory_stock_item` are reported under `@stock` key,
+/// so parents of composite products are notified when a child goes out of stock.
+pub struct ProductStockMapper;
+
+impl ChangeLogMapper<ProductChange> for ProductStockMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ProductChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("product_id", schema)
+                || row.is_changed_column("qty", schema)
+                || row.is_changed_column("is_in_stock", schema)
+                || row.is_changed_column("manage_stock", schema)
+                || row.is_changed_column("backorders", schema))
+            {
+                return Ok(());
+            }
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::Key(
+                row.parse("product_id", schema)?,
+                STOCK_KEY,
+            ))
+        })?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_insert_and_delete_into_stock_change() {
+        mapper_test!(
+            ProductStockMapper,
+            Some(ProductChange::Key(4, "@stock")),
+            insert[1, 4, 1, "10.0000", 1],
+            ["item_id", "product_id", "stock_id", "qty", "is_in_stock"]
+        );
+
+        mapper_test!(
+            ProductStockMapper,
+            Some(ProductChange::Key(5, "@stock")),
+            delete[2, 5, 1, "0.0000", 0],
+            ["item_id", "product_id", "stock_id", "qty", "is_in_stock"]
+        );
+    }
+
+    #[test]
+    fn maps_product_going_out_of_stock() {
+        mapper_test!(
+            ProductStockMapper,
+            Some(ProductChange::Key(4, "@stock")),
+            update[(1, 4, "1.0000", 1), (1, 4, "0.0000", 0)],
+            ["item_id", "product_id", "qty", "is_in_stock"]
+        );
+    }
+
+    #[test]
+    fn ignores_update_of_unrelated_stock_settings() {
+        mapper_test!(
+            ProductStockMapper,
+            None,
+            update[(1, 4, "1.0000", 1, "1.0000"), (1, 4, "1.0000", 1, "2.0000")],
+            ["item_id", "product_id", "qty", "is_in_stock", "min_sale_qty"]
+        );
+    }
+}
@@ -4,6 +4,10 @@ use crate::mapper::ChangeLogMapper;
 use crate::replication::Event;
 use crate::schema::TableSchema;
 
+/// Mapper for tier and group prices
+///
+/// Changes are scoped by website and by customer group,
+/// prices for all groups are reported without customer group scope.
 pub struct ProductTierPriceMapper;
 
 impl ChangeLogMapper<ProductChange> for ProductTierPriceMapper {
@@ -12,14 +16,30 @@ impl ChangeLogMapper<ProductChange> for ProductTierPriceMapper {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::InsertRow(row) | Event::DeleteRow(row) => {
-                Some(ProductChange::TierPrice(row.parse("entity_id", schema)?))
-            }
-            Event::UpdateRow(row) => {
-                Some(ProductChange::TierPrice(row.parse("entity_id", schema)?))
-            }
-        })
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::TierPrice(
+                row.parse("entity_id", schema)?,
+                row.parse("website_id", schema)?,
+                match row.parse::<usize>("all_groups", schema)? {
+                    0 => Some(row.parse("customer_group_id", schema)?),
+                    _ => None,
+                },
+            ))
+        })?);
+
+        Ok(())
     }
 }
 
@@ -31,19 +51,41 @@ mod tests {
     fn maps_insert_event_into_tier_price_field_update() {
         mapper_test!(
             ProductTierPriceMapper,
-            Some(ProductChange::TierPrice(2)),
-            insert[1, 2],
-            ["value_id", "entity_id"]
+            Some(ProductChange::TierPrice(2, 1, None)),
+            insert[1, 2, 1, 0, 1],
+            [
+                "value_id",
+                "entity_id",
+                "all_groups",
+                "customer_group_id",
+                "website_id"
+            ]
         );
     }
 
     #[test]
-    fn maps_update_event_without_any_field_match_into_update() {
-        mapper_test!(
+    fn maps_update_event_into_tier_price_change_of_both_websites() {
+        mapper_changes_test!(
+            ProductTierPriceMapper,
+            vec![
+                ProductChange::TierPrice(2, 1, Some(3)),
+                ProductChange::TierPrice(2, 2, Some(3))
+            ],
+            update[(1, 2, 0, 3), (2, 2, 0, 3)],
+            ["website_id", "entity_id", "all_groups", "customer_group_id"]
+        );
+    }
+
+    #[test]
+    fn maps_update_event_moving_price_to_another_product_for_both_products() {
+        mapper_changes_test!(
             ProductTierPriceMapper,
-            Some(ProductChange::TierPrice(2)),
-            update[(1, 2), (2, 2)],
-            ["website_id", "entity_id"]
+            vec![
+                ProductChange::TierPrice(2, 1, None),
+                ProductChange::TierPrice(4, 1, None)
+            ],
+            update[(1, 2, 1, 0, "5.00"), (1, 4, 1, 0, "5.00")],
+            ["website_id", "entity_id", "all_groups", "customer_group_id", "value"]
         );
     }
 
@@ -51,9 +93,15 @@ mod tests {
     fn maps_delete_event_into_tier_price_field_update() {
         mapper_test!(
             ProductTierPriceMapper,
-            Some(ProductChange::TierPrice(3)),
-            delete[1, 3],
-            ["value_id", "entity_id"]
+            Some(ProductChange::TierPrice(3, 0, Some(1))),
+            delete[1, 3, 0, 1, 0],
+            [
+                "value_id",
+                "entity_id",
+                "all_groups",
+                "customer_group_id",
+                "website_id"
+            ]
         );
     }
 }
@@ -12,13 +12,34 @@ impl ChangeLogMapper<ProductChange> for ProductWebsite {
         event: &Event,
         schema: &impl TableSchema,
     ) -> Result<Option<ProductChange>, Error> {
-        Ok(match event {
-            Event::InsertRow(row) | Event::DeleteRow(row) => Some(ProductChange::Website(
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ProductChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if !(row.is_changed_column("product_id", schema)
+                || row.is_changed_column("website_id", schema))
+            {
+                return Ok(());
+            }
+        }
+
+        changes.extend(event.map_owners(|row| {
+            Ok(ProductChange::Website(
                 row.parse("product_id", schema)?,
                 row.parse("website_id", schema)?,
-            )),
-            _ => None,
-        })
+            ))
+        })?);
+
+        Ok(())
     }
 }
 
@@ -27,10 +48,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn ignores_update_row_events() {
-        mapper_test!(
+    fn maps_update_row_event_for_both_website_assignments() {
+        mapper_changes_test!(
             ProductWebsite,
-            None,
+            vec![ProductChange::Website(1, 1), ProductChange::Website(1, 2)],
             update[(1, 1), (1, 2)],
             ["product_id", "website_id"]
         );
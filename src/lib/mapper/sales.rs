@@ -0,0 +1,213 @@
+use crate::aggregate::ChangeAggregateEntity;
+use crate::error::Error;
+use crate::log::{EntityChange, ItemChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Mapper for sales orders and quotes
+///
+/// Orders and quotes are reported under `@created`, `@deleted` and `@updated` keys,
+/// state and status transitions of an order are reported under separate `@state` and `@status` keys.
+/// Items, addresses, status history and sales documents are reported for their order or quote.
+///
+/// Table names of Magento 1 / OpenMage with `sales_flat_` prefix are supported as well.
+pub struct SalesMapper;
+
+impl SalesMapper {
+    fn map_entity(
+        event: &Event,
+        schema: &impl TableSchema,
+        entity: ChangeAggregateEntity,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let change = |key, id| ItemChange::from(EntityChange::Key(entity, id, key));
+
+        match event {
+            Event::InsertRow(row) => {
+                changes.push(change("@created", row.parse("entity_id", schema)?))
+            }
+            Event::DeleteRow(row) => {
+                changes.push(change("@deleted", row.parse("entity_id", schema)?))
+            }
+            Event::UpdateRow(row) if row.is_changed_column("entity_id", schema) => {
+                changes.push(change("@deleted", row.parse("entity_id", schema)?));
+                changes.push(change("@created", row.parse_changed("entity_id", schema)?));
+            }
+            Event::UpdateRow(row) => {
+                let entity_id = row.parse("entity_id", schema)?;
+
+                changes.push(change("@updated", entity_id));
+                changes.extend(
+                    [("state", "@state"), ("status", "@status")]
+                        .into_iter()
+                        .filter(|(column, _)| row.is_changed_column(column, schema))
+                        .map(|(_, key)| change(key, entity_id)),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn map_child(
+        event: &Event,
+        schema: &impl TableSchema,
+        entity: ChangeAggregateEntity,
+        column: &str,
+        key: &'static str,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let owners = event
+            .map_owners(|row| Ok(EntityChange::Key(entity, row.parse(column, schema)?, key)))?;
+
+        changes.extend(owners.into_iter().map(ItemChange::EntityChange));
+
+        Ok(())
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for SalesMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let table_name = schema.table_name();
+        let table_name = table_name
+            .strip_prefix("sales_flat_")
+            .or_else(|| table_name.strip_prefix("sales_"))
+            .unwrap_or(table_name);
+
+        let (entity, column, key) = match table_name {
+            "order" => {
+                return Self::map_entity(event, schema, ChangeAggregateEntity::Order, changes)
+            }
+            "quote" => {
+                return Self::map_entity(event, schema, ChangeAggregateEntity::Quote, changes)
+            }
+            "order_item" => (ChangeAggregateEntity::Order, "order_id", "@item"),
+            "order_address" => (ChangeAggregateEntity::Order, "parent_id", "@address"),
+            "order_status_history" => {
+                (ChangeAggregateEntity::Order, "parent_id", "@status_history")
+            }
+            "invoice" => (ChangeAggregateEntity::Order, "order_id", "@invoice"),
+            "shipment" => (ChangeAggregateEntity::Order, "order_id", "@shipment"),
+            "creditmemo" => (ChangeAggregateEntity::Order, "order_id", "@creditmemo"),
+            "quote_item" => (ChangeAggregateEntity::Quote, "quote_id", "@item"),
+            "quote_address" => (ChangeAggregateEntity::Quote, "quote_id", "@address"),
+            _ => return Ok(()),
+        };
+
+        Self::map_child(event, schema, entity, column, key, changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn order_change(key: &'static str, id: usize) -> ItemChange {
+        ItemChange::EntityChange(EntityChange::Key(ChangeAggregateEntity::Order, id, key))
+    }
+
+    #[test]
+    fn maps_created_order() {
+        mapper_test!(
+            SalesMapper,
+            Some(order_change("@created", 10)),
+            Event::InsertRow(binlog_row!(10, "new", "pending")),
+            test_table!("sales_order", ["entity_id", "state", "status"])
+        );
+    }
+
+    #[test]
+    fn maps_state_and_status_transitions_as_separate_keys() {
+        mapper_changes_test!(
+            SalesMapper,
+            vec![
+                order_change("@updated", 10),
+                order_change("@state", 10),
+                order_change("@status", 10)
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(10, "new", "pending"),
+                binlog_row!(10, "processing", "processing")
+            )),
+            test_table!("sales_order", ["entity_id", "state", "status"])
+        );
+    }
+
+    #[test]
+    fn maps_status_transition_without_state_change() {
+        mapper_changes_test!(
+            SalesMapper,
+            vec![order_change("@updated", 10), order_change("@status", 10)],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(10, "processing", "processing"),
+                binlog_row!(10, "processing", "fraud")
+            )),
+            test_table!("sales_order", ["entity_id", "state", "status"])
+        );
+    }
+
+    #[test]
+    fn maps_order_documents_to_order() {
+        mapper_test!(
+            SalesMapper,
+            Some(order_change("@shipment", 10)),
+            Event::InsertRow(binlog_row!(3, 10, 1)),
+            test_table!("sales_shipment", ["entity_id", "order_id", "store_id"])
+        );
+    }
+
+    #[test]
+    fn maps_status_history_to_order() {
+        mapper_test!(
+            SalesMapper,
+            Some(order_change("@status_history", 10)),
+            Event::InsertRow(binlog_row!(3, 10, "Comment")),
+            test_table!(
+                "sales_order_status_history",
+                ["entity_id", "parent_id", "comment"]
+            )
+        );
+    }
+
+    #[test]
+    fn maps_order_item_of_openmage_table() {
+        mapper_test!(
+            SalesMapper,
+            Some(order_change("@item", 10)),
+            Event::DeleteRow(binlog_row!(3, 10, "sku1")),
+            test_table!("sales_flat_order_item", ["item_id", "order_id", "sku"])
+        );
+    }
+
+    #[test]
+    fn maps_quote_item_to_quote() {
+        mapper_test!(
+            SalesMapper,
+            Some(ItemChange::EntityChange(EntityChange::Key(
+                ChangeAggregateEntity::Quote,
+                5,
+                "@item"
+            ))),
+            Event::InsertRow(binlog_row!(3, 5, "sku1")),
+            test_table!("quote_item", ["item_id", "quote_id", "sku"])
+        );
+    }
+}
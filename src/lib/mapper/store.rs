@@ -0,0 +1,184 @@
+use crate::aggregate::ChangeAggregateEntity;
+use crate::error::Error;
+use crate::log::{EntityChange, ItemChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+/// Mapper for store views, store groups and websites
+///
+/// Store views and groups are reported under `@store` and `@group` keys scoped by website,
+/// websites under `@website` key. Changes of default group of a website and default store
+/// of a group are reported under separate `@default_group` and `@default_store` keys.
+///
+/// Table names of Magento 1 / OpenMage are supported as well.
+pub struct StoreMapper;
+
+impl StoreMapper {
+    fn map_change(
+        event: &Event,
+        schema: &impl TableSchema,
+        id: &str,
+        key: &'static str,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let owners = event.map_owners(|row| {
+            let entity_id = row.parse(id, schema)?;
+
+            Ok(match id {
+                "website_id" => EntityChange::Key(ChangeAggregateEntity::Store, entity_id, key),
+                _ => EntityChange::ScopedKey(
+                    ChangeAggregateEntity::Store,
+                    entity_id,
+                    key,
+                    row.parse("website_id", schema)?,
+                ),
+            })
+        })?;
+
+        changes.extend(owners.into_iter().map(ItemChange::EntityChange));
+
+        Ok(())
+    }
+
+    fn map_default(
+        event: &Event,
+        schema: &impl TableSchema,
+        id: &str,
+        column: &str,
+        key: &'static str,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        if let Event::UpdateRow(row) = event {
+            if row.is_changed_column(column, schema) {
+                changes.push(ItemChange::EntityChange(EntityChange::Key(
+                    ChangeAggregateEntity::Store,
+                    row.parse_changed(id, schema)?,
+                    key,
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for StoreMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        match schema.table_name() {
+            "store" | "core_store" => {
+                Self::map_change(event, schema, "store_id", "@store", changes)
+            }
+            "store_group" | "core_store_group" => {
+                Self::map_change(event, schema, "group_id", "@group", changes)?;
+                Self::map_default(
+                    event,
+                    schema,
+                    "group_id",
+                    "default_store_id",
+                    "@default_store",
+                    changes,
+                )
+            }
+            "store_website" | "core_website" => {
+                Self::map_change(event, schema, "website_id", "@website", changes)?;
+                Self::map_default(
+                    event,
+                    schema,
+                    "website_id",
+                    "default_group_id",
+                    "@default_group",
+                    changes,
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn store_change(key: &'static str, id: usize, website_id: usize) -> ItemChange {
+        ItemChange::EntityChange(EntityChange::ScopedKey(
+            ChangeAggregateEntity::Store,
+            id,
+            key,
+            website_id,
+        ))
+    }
+
+    fn website_change(key: &'static str, id: usize) -> ItemChange {
+        ItemChange::EntityChange(EntityChange::Key(ChangeAggregateEntity::Store, id, key))
+    }
+
+    #[test]
+    fn maps_new_store_view_scoped_by_website() {
+        mapper_test!(
+            StoreMapper,
+            Some(store_change("@store", 3, 1)),
+            Event::InsertRow(binlog_row!(3, "nl", 1, 1)),
+            test_table!("store", ["store_id", "code", "website_id", "group_id"])
+        );
+    }
+
+    #[test]
+    fn maps_store_view_moved_to_another_website_for_both_websites() {
+        mapper_changes_test!(
+            StoreMapper,
+            vec![store_change("@store", 3, 1), store_change("@store", 3, 2)],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(3, "nl", 1, 1),
+                binlog_row!(3, "nl", 2, 2)
+            )),
+            test_table!("store", ["store_id", "code", "website_id", "group_id"])
+        );
+    }
+
+    #[test]
+    fn maps_default_group_change_of_website() {
+        mapper_changes_test!(
+            StoreMapper,
+            vec![
+                website_change("@website", 1),
+                website_change("@default_group", 1)
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, "base", 1),
+                binlog_row!(1, "base", 2)
+            )),
+            test_table!("store_website", ["website_id", "code", "default_group_id"])
+        );
+    }
+
+    #[test]
+    fn maps_store_group_of_openmage_table() {
+        mapper_test!(
+            StoreMapper,
+            Some(store_change("@group", 2, 1)),
+            Event::DeleteRow(binlog_row!(2, 1, "Main Store", 1)),
+            test_table!(
+                "core_store_group",
+                ["group_id", "website_id", "name", "default_store_id"]
+            )
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use crate::error::Error;
+use crate::log::{ItemChange, ProductChange};
+use crate::mapper::ChangeLogMapper;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Table mapping declared in application configuration
+///
+/// Each row change in `table` is reported as a change of product in `entity_id` column
+/// under `key` in aggregate, optionally scoped by integer value of `scope` column.
+/// When `columns` are provided, update is reported only if one of them has changed.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct TableMapping {
+    table: String,
+    entity_id: String,
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default)]
+    columns: Vec<String>,
+    key: String,
+}
+
+impl TableMapping {
+    pub fn new(
+        table: impl Into<String>,
+        entity_id: impl Into<String>,
+        key: impl Into<String>,
+    ) -> Self {
+        Self {
+            table: table.into(),
+            entity_id: entity_id.into(),
+            scope: None,
+            columns: Vec::new(),
+            key: key.into(),
+        }
+    }
+
+    pub fn with_scope(self, scope: impl Into<String>) -> Self {
+        Self {
+            scope: Some(scope.into()),
+            ..self
+        }
+    }
+
+    pub fn with_columns<I>(self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+}
+
+struct TableMappingRule {
+    entity_id: String,
+    scope: Option<String>,
+    columns: Vec<String>,
+    key: &'static str,
+}
+
+/// Mapper for tables declared in configuration
+#[derive(Default)]
+pub struct TableMappingMapper {
+    rules: HashMap<String, TableMappingRule>,
+}
+
+/// Static string of configured aggregate key
+///
+/// Aggregate keys are static strings, so each distinct key is allocated once per process
+/// and shared by mappers of all tenants.
+fn intern(key: &str) -> &'static str {
+    static KEYS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut keys = KEYS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(key) = keys.get(key) {
+        return key;
+    }
+
+    let key: &'static str = Box::leak(key.into());
+    keys.insert(key);
+    key
+}
+
+impl<'a, I> From<I> for TableMappingMapper
+where
+    I: IntoIterator<Item = &'a TableMapping>,
+{
+    fn from(mappings: I) -> Self {
+        Self {
+            rules: mappings
+                .into_iter()
+                .map(|mapping| {
+                    (
+                        mapping.table.clone(),
+                        TableMappingRule {
+                            entity_id: mapping.entity_id.clone(),
+                            scope: mapping.scope.clone(),
+                            columns: mapping.columns.clone(),
+                            key: intern(&mapping.key),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TableMappingMapper {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl TableMappingRule {
+    fn map_row(&self, entity_id: usize, scope: Option<usize>) -> ProductChange {
+        match scope {
+            Some(scope) => ProductChange::ScopedKey(entity_id, self.key, scope),
+            None => ProductChange::Key(entity_id, self.key),
+        }
+    }
+
+    fn is_changed(&self, event: &Event, schema: &impl TableSchema) -> bool {
+        match event {
+            Event::UpdateRow(row) => {
+                self.columns.is_empty()
+                    || row.is_changed_column(&self.entity_id, schema)
+                    || self
+                        .scope
+                        .as_ref()
+                        .is_some_and(|scope| row.is_changed_column(scope, schema))
+                    || self
+                        .columns
+                        .iter()
+                        .any(|column| row.is_changed_column(column, schema))
+            }
+            _ => true,
+        }
+    }
+}
+
+impl ChangeLogMapper<ItemChange> for TableMappingMapper {
+    fn map_event(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Option<ItemChange>, Error> {
+        let mut changes = Vec::new();
+        self.map_event_into(event, schema, &mut changes)?;
+
+        Ok(changes.into_iter().next())
+    }
+
+    fn map_event_into(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+        changes: &mut Vec<ItemChange>,
+    ) -> Result<(), Error> {
+        let rule = match self.rules.get(schema.table_name()) {
+            Some(rule) if rule.is_changed(event, schema) => rule,
+            _ => return Ok(()),
+        };
+
+        let owners = event.map_owners(|row| {
+            Ok(rule.map_row(
+                row.parse(&rule.entity_id, schema)?,
+                match &rule.scope {
+                    Some(scope) => Some(row.parse(scope, schema)?),
+                    None => None,
+                },
+            ))
+        })?;
+
+        changes.extend(owners.into_iter().map(ItemChange::ProductChange));
+
+        Ok(())
+    }
+
+    fn is_mapped_table(&self, table_name: &str) -> bool {
+        self.rules.contains_key(table_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn mapper() -> TableMappingMapper {
+        TableMappingMapper::from(&[
+            TableMapping::new("custom_product_flag", "product_id", "custom_flag")
+                .with_columns(["flag"]),
+            TableMapping::new("custom_product_store", "product_id", "custom_store")
+                .with_scope("store_id"),
+        ])
+    }
+
+    #[test]
+    fn ignores_tables_that_are_not_declared() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::InsertRow(binlog_row!(1, 2)),
+            test_table!("catalog_product_entity", ["entity_id", "flag"])
+        );
+    }
+
+    #[test]
+    fn maps_insert_into_declared_key() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::Key(
+                1,
+                "custom_flag"
+            ))),
+            Event::InsertRow(binlog_row!(1, 2)),
+            test_table!("custom_product_flag", ["product_id", "flag"])
+        );
+    }
+
+    #[test]
+    fn maps_delete_into_scoped_key() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::ScopedKey(
+                3,
+                "custom_store",
+                2
+            ))),
+            Event::DeleteRow(binlog_row!(3, 2, "value")),
+            test_table!("custom_product_store", ["product_id", "store_id", "value"])
+        );
+    }
+
+    #[test]
+    fn ignores_update_when_declared_columns_are_not_changed() {
+        mapper_test!(
+            mapper(),
+            None,
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 2, "one"),
+                binlog_row!(1, 2, "two")
+            )),
+            test_table!("custom_product_flag", ["product_id", "flag", "note"])
+        );
+    }
+
+    #[test]
+    fn maps_update_when_declared_column_is_changed() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::Key(
+                1,
+                "custom_flag"
+            ))),
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 2, "one"),
+                binlog_row!(1, 3, "one")
+            )),
+            test_table!("custom_product_flag", ["product_id", "flag", "note"])
+        );
+    }
+
+    #[test]
+    fn maps_update_moving_row_to_another_product_for_both_products() {
+        mapper_changes_test!(
+            mapper(),
+            vec![
+                ItemChange::ProductChange(ProductChange::Key(1, "custom_flag")),
+                ItemChange::ProductChange(ProductChange::Key(2, "custom_flag"))
+            ],
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(1, 2, "one"),
+                binlog_row!(2, 2, "one")
+            )),
+            test_table!("custom_product_flag", ["product_id", "flag", "note"])
+        );
+    }
+
+    #[test]
+    fn maps_any_update_when_no_columns_declared() {
+        mapper_test!(
+            mapper(),
+            Some(ItemChange::ProductChange(ProductChange::ScopedKey(
+                3,
+                "custom_store",
+                1
+            ))),
+            Event::UpdateRow(UpdateRowEvent::new(
+                binlog_row!(3, 1, "one"),
+                binlog_row!(3, 1, "two")
+            )),
+            test_table!("custom_product_store", ["product_id", "store_id", "value"])
+        );
+    }
+
+    #[test]
+    fn shares_configured_key_between_mappers() {
+        let (first, second) = (intern("custom_flag"), intern(&String::from("custom_flag")));
+
+        assert!(std::ptr::eq(first, second));
+    }
+}
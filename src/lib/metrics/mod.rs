@@ -0,0 +1,333 @@
+//! Runtime metrics of the changelog in Prometheus text format
+mod output;
+mod sender;
+mod server;
+
+use crate::replication::ReplicationLag;
+pub use output::{CountingWriter, MetricsOutput};
+pub use sender::MetricsSender;
+pub use server::serve_metrics;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Shared metrics collected by replication client, mappers and writer
+///
+/// All the clones share the same counters
+#[derive(Clone, Default)]
+pub struct Metrics {
+    state: Arc<MetricsState>,
+    lag: ReplicationLag,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    events_read: Mutex<HashMap<String, u64>>,
+    items_mapped: Mutex<HashMap<&'static str, u64>>,
+    aggregates_flushed: AtomicU64,
+    output_bytes: AtomicU64,
+    channel_depth: AtomicU64,
+    reconnects: AtomicU64,
+    connected: AtomicBool,
+}
+
+/// Status reported by health endpoint
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Health {
+    pub connected: bool,
+    pub progressing: bool,
+}
+
+impl Health {
+    pub fn is_healthy(&self) -> bool {
+        self.connected && self.progressing
+    }
+}
+
+impl Metrics {
+    pub fn lag(&self) -> &ReplicationLag {
+        &self.lag
+    }
+
+    pub fn record_event(&self, table: &str) {
+        if let Ok(mut events) = self.state.events_read.lock() {
+            match events.get_mut(table) {
+                Some(count) => *count += 1,
+                None => {
+                    events.insert(table.to_owned(), 1);
+                }
+            }
+        }
+    }
+
+    pub fn record_item_mapped(&self, kind: &'static str) {
+        if let Ok(mut items) = self.state.items_mapped.lock() {
+            *items.entry(kind).or_default() += 1;
+        }
+    }
+
+    pub fn record_aggregate_flushed(&self) {
+        self.state
+            .aggregates_flushed
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_output_bytes(&self, bytes: usize) {
+        self.state
+            .output_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_reconnect(&self) {
+        self.state.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Item is sent into writer channel
+    pub fn increment_channel_depth(&self) {
+        self.state.channel_depth.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Item is received from writer channel
+    pub fn decrement_channel_depth(&self) {
+        let _ =
+            self.state
+                .channel_depth
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |depth| {
+                    Some(depth.saturating_sub(1))
+                });
+    }
+
+    pub fn set_connected(&self, connected: bool) {
+        self.state.connected.store(connected, Ordering::Relaxed);
+    }
+
+    /// Stream is progressing when event or heartbeat was read within `stale_after` duration
+    pub fn health(&self, stale_after: Duration) -> Health {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        self.health_at(now, stale_after)
+    }
+
+    pub(crate) fn health_at(&self, now: u64, stale_after: Duration) -> Health {
+        Health {
+            connected: self.state.connected.load(Ordering::Relaxed),
+            progressing: match self.lag.last_event_at() {
+                Some(last_event_at) => now.saturating_sub(last_event_at) <= stale_after.as_secs(),
+                None => false,
+            },
+        }
+    }
+
+    /// Renders metrics in Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        let events: BTreeMap<String, u64> = match self.state.events_read.lock() {
+            Ok(events) => events.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            Err(_) => BTreeMap::new(),
+        };
+
+        let items: BTreeMap<&'static str, u64> = match self.state.items_mapped.lock() {
+            Ok(items) => items.iter().map(|(k, v)| (*k, *v)).collect(),
+            Err(_) => BTreeMap::new(),
+        };
+
+        write_header(
+            &mut output,
+            "changelog_events_read_total",
+            "counter",
+            "Binlog row events read per table",
+        );
+        for (table, count) in events {
+            let _ = writeln!(
+                output,
+                "changelog_events_read_total{{table=\"{table}\"}} {count}"
+            );
+        }
+
+        write_header(
+            &mut output,
+            "changelog_items_mapped_total",
+            "counter",
+            "Changes produced by mappers per change type",
+        );
+        for (change, count) in items {
+            let _ = writeln!(
+                output,
+                "changelog_items_mapped_total{{change=\"{change}\"}} {count}"
+            );
+        }
+
+        write_metric(
+            &mut output,
+            "changelog_aggregates_flushed_total",
+            "counter",
+            "Aggregates written into output",
+            self.state.aggregates_flushed.load(Ordering::Relaxed),
+        );
+        write_metric(
+            &mut output,
+            "changelog_output_bytes_total",
+            "counter",
+            "Bytes written into output",
+            self.state.output_bytes.load(Ordering::Relaxed),
+        );
+        write_metric(
+            &mut output,
+            "changelog_channel_depth",
+            "gauge",
+            "Changes waiting in writer channel",
+            self.state.channel_depth.load(Ordering::Relaxed),
+        );
+        write_metric(
+            &mut output,
+            "changelog_reconnects_total",
+            "counter",
+            "Reconnects of binlog stream",
+            self.state.reconnects.load(Ordering::Relaxed),
+        );
+        write_metric(
+            &mut output,
+            "changelog_connected",
+            "gauge",
+            "Binlog stream is connected",
+            self.state.connected.load(Ordering::Relaxed) as u64,
+        );
+        write_metric(
+            &mut output,
+            "changelog_lag_seconds",
+            "gauge",
+            "Seconds between last event timestamp and time it was read",
+            self.lag.seconds_behind(),
+        );
+
+        if let Some(bytes) = self.lag.bytes_behind() {
+            write_metric(
+                &mut output,
+                "changelog_lag_bytes",
+                "gauge",
+                "Bytes behind source server position in the same binlog file",
+                bytes,
+            );
+        }
+
+        if let Some(files) = self.lag.files_behind() {
+            write_metric(
+                &mut output,
+                "changelog_lag_files",
+                "gauge",
+                "Binlog files behind source server position",
+                files,
+            );
+        }
+
+        output
+    }
+}
+
+fn write_header(output: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} {kind}");
+}
+
+fn write_metric(output: &mut String, name: &str, kind: &str, help: &str, value: u64) {
+    write_header(output, name, kind, help);
+    let _ = writeln!(output, "{name} {value}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::BinlogPosition;
+
+    #[test]
+    fn renders_counters_per_table_and_change_type() {
+        let metrics = Metrics::default();
+
+        metrics.record_event("catalog_product_entity");
+        metrics.record_event("catalog_product_entity");
+        metrics.record_event("catalog_category_product");
+        metrics.record_item_mapped("attribute");
+
+        let output = metrics.render();
+
+        assert!(output.contains(
+            "changelog_events_read_total{table=\"catalog_category_product\"} 1\n\
+            changelog_events_read_total{table=\"catalog_product_entity\"} 2\n"
+        ));
+        assert!(output.contains("changelog_items_mapped_total{change=\"attribute\"} 1\n"));
+    }
+
+    #[test]
+    fn renders_writer_and_connection_metrics() {
+        let metrics = Metrics::default();
+
+        metrics.record_aggregate_flushed();
+        metrics.record_output_bytes(120);
+        metrics.increment_channel_depth();
+        metrics.increment_channel_depth();
+        metrics.decrement_channel_depth();
+        metrics.record_reconnect();
+        metrics.set_connected(true);
+
+        let output = metrics.render();
+
+        assert!(output.contains("changelog_aggregates_flushed_total 1\n"));
+        assert!(output.contains("changelog_output_bytes_total 120\n"));
+        assert!(output.contains("changelog_channel_depth 1\n"));
+        assert!(output.contains("changelog_reconnects_total 1\n"));
+        assert!(output.contains("changelog_connected 1\n"));
+    }
+
+    #[test]
+    fn renders_position_lag_only_when_known() {
+        let metrics = Metrics::default();
+
+        assert!(!metrics.render().contains("changelog_lag_bytes"));
+
+        metrics
+            .lag()
+            .observe_heartbeat(&BinlogPosition::new("bin.000001", 100));
+        metrics
+            .lag()
+            .observe_master_position(BinlogPosition::new("bin.000001", 150));
+
+        assert!(metrics.render().contains("changelog_lag_bytes 50\n"));
+    }
+
+    #[test]
+    fn reports_health_from_connection_and_last_event() {
+        let metrics = Metrics::default();
+
+        assert_eq!(
+            metrics.health_at(1000, Duration::from_secs(60)),
+            Health {
+                connected: false,
+                progressing: false
+            }
+        );
+
+        metrics.set_connected(true);
+        metrics
+            .lag()
+            .observe_event_at(990, &BinlogPosition::new("bin.000001", 4), 1000);
+
+        assert_eq!(
+            vec![
+                metrics
+                    .health_at(1030, Duration::from_secs(60))
+                    .is_healthy(),
+                metrics
+                    .health_at(1100, Duration::from_secs(60))
+                    .is_healthy(),
+            ],
+            vec![true, false]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::aggregate::ChangeAggregate;
+use crate::error::Error;
+use crate::metrics::Metrics;
+use crate::output::Output;
+
+use std::io::Result as IoResult;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::AsyncWrite;
+
+/// Output that records number of written aggregates and bytes
+#[derive(Clone)]
+pub struct MetricsOutput<O> {
+    inner: O,
+    metrics: Metrics,
+}
+
+impl<O> MetricsOutput<O>
+where
+    O: Output,
+{
+    pub fn new(inner: O, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+impl<O> Output for MetricsOutput<O>
+where
+    O: Output,
+{
+    async fn write<T: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut T,
+        aggregate: ChangeAggregate,
+    ) -> Result<(), Error> {
+        let mut writer = CountingWriter::new(writer, &self.metrics);
+
+        self.inner.write(&mut writer, aggregate).await?;
+        self.metrics.record_aggregate_flushed();
+
+        Ok(())
+    }
+}
+
+/// Writer that records number of bytes passed to the inner writer
+pub struct CountingWriter<'a, W> {
+    inner: &'a mut W,
+    metrics: &'a Metrics,
+}
+
+impl<'a, W> CountingWriter<'a, W> {
+    pub fn new(inner: &'a mut W, metrics: &'a Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+impl<W> AsyncWrite for CountingWriter<'_, W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<IoResult<usize>> {
+        let result = Pin::new(&mut *self.inner).poll_write(cx, buf);
+
+        if let Poll::Ready(Ok(bytes)) = result {
+            self.metrics.record_output_bytes(bytes);
+        }
+
+        result
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut *self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut *self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::ChangeAggregateEntity;
+    use crate::output::JsonOutput;
+    use crate::replication::{BinlogPosition, EventMetadata};
+    use std::io::Cursor;
+
+    #[tokio::test]
+    async fn records_aggregates_and_bytes_written_into_output() {
+        let metrics = Metrics::default();
+        let output = MetricsOutput::new(JsonOutput, metrics.clone());
+        let mut writer = Cursor::new(Vec::new());
+
+        output
+            .write(
+                &mut writer,
+                ChangeAggregate::new(
+                    ChangeAggregateEntity::Product,
+                    EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+                ),
+            )
+            .await
+            .unwrap();
+
+        let written = writer.into_inner().len();
+        let rendered = metrics.render();
+
+        assert!(rendered.contains("changelog_aggregates_flushed_total 1\n"));
+        assert!(rendered.contains(&format!("changelog_output_bytes_total {written}\n")));
+    }
+}
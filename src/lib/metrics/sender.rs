@@ -0,0 +1,69 @@
+use crate::error::Error;
+use crate::log::{ChangeLogSender, ItemChange};
+use crate::metrics::Metrics;
+
+/// Change log sender that records mapped changes and depth of the writer channel
+#[derive(Clone)]
+pub struct MetricsSender<S> {
+    inner: S,
+    metrics: Metrics,
+}
+
+impl<S> MetricsSender<S>
+where
+    S: ChangeLogSender<Item = ItemChange>,
+{
+    pub fn new(inner: S, metrics: Metrics) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+impl<S> ChangeLogSender for MetricsSender<S>
+where
+    S: ChangeLogSender<Item = ItemChange>,
+{
+    type Item = ItemChange;
+
+    async fn send(&self, change: Self::Item) -> Result<(), Error> {
+        match &change {
+            ItemChange::ProductChange(change) => self.metrics.record_item_mapped(change.kind()),
+            ItemChange::EntityChange(change) => self.metrics.record_item_mapped(change.kind()),
+            ItemChange::ConfigChange(change) => self.metrics.record_item_mapped(change.kind()),
+            ItemChange::Metadata(_) => {}
+        }
+
+        self.metrics.increment_channel_depth();
+        self.inner.send(change).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::ProductChange;
+    use crate::test_util::TestChangeLogSender;
+
+    #[tokio::test]
+    async fn records_mapped_product_changes_by_kind() {
+        let metrics = Metrics::default();
+        let inner = TestChangeLogSender::default();
+        let sender = MetricsSender::new(inner.clone(), metrics.clone());
+
+        sender
+            .send(ProductChange::Attribute(1, 2, 0).into())
+            .await
+            .unwrap();
+        sender
+            .send(ProductChange::Attribute(2, 2, 0).into())
+            .await
+            .unwrap();
+        sender.send(ProductChange::Created(3).into()).await.unwrap();
+
+        let rendered = metrics.render();
+
+        assert_eq!(inner.values().await.len(), 3);
+        assert!(rendered.contains("changelog_items_mapped_total{change=\"attribute\"} 2\n"));
+        assert!(rendered.contains("changelog_items_mapped_total{change=\"created\"} 1\n"));
+        assert!(rendered.contains("changelog_channel_depth 3\n"));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::error::Error;
+use crate::metrics::Metrics;
+
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const REQUEST_BUFFER_SIZE: usize = 1024;
+
+/// Serves `/metrics` and `/healthz` endpoints over plain HTTP
+///
+/// Stream is reported as healthy when it is connected
+/// and an event or heartbeat was read within `stale_after` duration
+pub async fn serve_metrics(
+    listener: TcpListener,
+    metrics: Metrics,
+    stale_after: Duration,
+) -> Result<(), Error> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let metrics = metrics.clone();
+
+        tokio::spawn(async move { respond(stream, &metrics, stale_after).await });
+    }
+}
+
+async fn respond(
+    mut stream: TcpStream,
+    metrics: &Metrics,
+    stale_after: Duration,
+) -> Result<(), Error> {
+    let mut buffer = [0; REQUEST_BUFFER_SIZE];
+    let length = stream.read(&mut buffer).await?;
+    let request = String::from_utf8_lossy(&buffer[..length]);
+
+    let (status, content_type, body) = route(request_path(&request), metrics, stale_after);
+
+    stream
+        .write_all(
+            format!(
+                "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .as_bytes(),
+        )
+        .await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+fn request_path(request: &str) -> &str {
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    match path.split_once('?') {
+        Some((path, _)) => path,
+        None => path,
+    }
+}
+
+fn route(
+    path: &str,
+    metrics: &Metrics,
+    stale_after: Duration,
+) -> (&'static str, &'static str, String) {
+    match path {
+        "/metrics" => ("200 OK", "text/plain; version=0.0.4", metrics.render()),
+        "/healthz" => {
+            let health = metrics.health(stale_after);
+            let body = json!({
+                "connected": health.connected,
+                "progressing": health.progressing,
+            })
+            .to_string();
+
+            match health.is_healthy() {
+                true => ("200 OK", "application/json", body),
+                false => ("503 Service Unavailable", "application/json", body),
+            }
+        }
+        _ => ("404 Not Found", "text/plain", String::from("Not Found")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::BinlogPosition;
+
+    #[test]
+    fn takes_path_from_request_line() {
+        assert_eq!(
+            vec![
+                request_path("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+                request_path("GET /healthz?verbose=1 HTTP/1.1\r\n\r\n"),
+                request_path(""),
+            ],
+            vec!["/metrics", "/healthz", "/"]
+        );
+    }
+
+    #[test]
+    fn serves_metrics_in_text_format() {
+        let metrics = Metrics::default();
+        metrics.record_aggregate_flushed();
+
+        let (status, _, body) = route("/metrics", &metrics, Duration::from_secs(60));
+
+        assert_eq!(status, "200 OK");
+        assert!(body.contains("changelog_aggregates_flushed_total 1\n"));
+    }
+
+    #[test]
+    fn reports_unavailable_health_when_stream_is_not_connected() {
+        let (status, _, body) = route("/healthz", &Metrics::default(), Duration::from_secs(60));
+
+        assert_eq!(
+            (status, body.as_str()),
+            (
+                "503 Service Unavailable",
+                r#"{"connected":false,"progressing":false}"#
+            )
+        );
+    }
+
+    #[test]
+    fn reports_healthy_stream_when_connected_and_progressing() {
+        let metrics = Metrics::default();
+        metrics.set_connected(true);
+        metrics
+            .lag()
+            .observe_heartbeat(&BinlogPosition::new("bin.000001", 4));
+
+        let (status, _, _) = route("/healthz", &metrics, Duration::from_secs(60));
+
+        assert_eq!(status, "200 OK");
+    }
+
+    #[test]
+    fn responds_with_not_found_for_unknown_path() {
+        let (status, _, _) = route("/unknown", &Metrics::default(), Duration::from_secs(60));
+
+        assert_eq!(status, "404 Not Found");
+    }
+}
@@ -11,6 +11,7 @@ pub mod database;
 pub mod error;
 pub mod log;
 pub mod mapper;
+pub mod metrics;
 pub mod output;
 pub mod schema;
 
@@ -14,6 +14,14 @@ fn entity_to_str(entity: ChangeAggregateEntity) -> &'static str {
         ChangeAggregateEntity::Product => "product",
         ChangeAggregateEntity::Category => "category",
         ChangeAggregateEntity::Inventory => "inventory",
+        ChangeAggregateEntity::CatalogRule => "catalog_rule",
+        ChangeAggregateEntity::Cms => "cms",
+        ChangeAggregateEntity::Customer => "customer",
+        ChangeAggregateEntity::Order => "order",
+        ChangeAggregateEntity::Quote => "quote",
+        ChangeAggregateEntity::Config => "config",
+        ChangeAggregateEntity::Store => "store",
+        ChangeAggregateEntity::Eav => "eav",
     }
 }
 
@@ -32,6 +40,18 @@ impl Output for JsonOutput {
             }
         });
 
+        if let Some(gtid) = aggregate.metadata.gtid() {
+            populate_global_level_key(&mut json_object, "metadata", "gtid", gtid.to_string())?;
+        }
+
+        if let Some(statement) = aggregate.metadata.statement() {
+            populate_global_level_key(&mut json_object, "metadata", "statement", statement)?;
+        }
+
+        if let Some(tenant) = aggregate.metadata.tenant() {
+            populate_global_level_key(&mut json_object, "metadata", "tenant", tenant)?;
+        }
+
         for (key, value) in aggregate.data {
             match key {
                 ChangeAggregateKey::Key(key) => {
@@ -46,6 +66,30 @@ impl Output for JsonOutput {
                 ChangeAggregateKey::Attribute(id) => {
                     populate_global_level_key(&mut json_object, "attribute", id, value)?
                 }
+                ChangeAggregateKey::AttributeCode(code, id) => {
+                    populate_global_level_key(&mut json_object, "attribute_id", &code, id)?;
+                    populate_global_level_key(&mut json_object, "attribute", code, value)?
+                }
+                ChangeAggregateKey::AttributeCodeAndScopeInt(code, id, scope) => {
+                    populate_global_level_key(&mut json_object, "attribute_id", &code, id)?;
+                    populate_scoped_level_key(
+                        &mut json_object,
+                        "scoped_attribute",
+                        code,
+                        scope,
+                        value,
+                    )?
+                }
+                ChangeAggregateKey::PathAndScopeInt(path, scope, id) => {
+                    populate_scoped_path_key(&mut json_object, "value", scope, id, path, value)?
+                }
+                ChangeAggregateKey::AttributeAndScopeInt(id, scope) => populate_scoped_level_key(
+                    &mut json_object,
+                    "scoped_attribute",
+                    id,
+                    scope,
+                    value,
+                )?,
             }
         }
 
@@ -99,11 +143,38 @@ fn populate_scoped_level_key(
     Ok(())
 }
 
+fn populate_scoped_path_key(
+    json: &mut Value,
+    group: impl ToString,
+    scope: impl ToString,
+    scope_id: impl ToString,
+    path: impl ToString,
+    value: impl Into<Value>,
+) -> Result<(), Error> {
+    json.as_object_mut()
+        .ok_or(Error::OutputError)?
+        .entry(group.to_string())
+        .or_insert(json!({}))
+        .as_object_mut()
+        .ok_or(Error::OutputError)?
+        .entry(scope.to_string())
+        .or_insert(json!({}))
+        .as_object_mut()
+        .ok_or(Error::OutputError)?
+        .entry(scope_id.to_string())
+        .or_insert(json!({}))
+        .as_object_mut()
+        .ok_or(Error::OutputError)?
+        .insert(path.to_string(), value.into());
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::aggregate::{ChangeAggregateEntity, ChangeAggregateKey};
-    use crate::replication::{BinlogPosition, EventMetadata};
+    use crate::aggregate::{ChangeAggregateEntity, ChangeAggregateKey, ChangeAggregateValue};
+    use crate::replication::{BinlogPosition, EventMetadata, MariaDbGtid};
     use serde_json::from_slice;
     use std::io::Cursor;
 
@@ -134,6 +205,189 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn outputs_catalog_rule_entity() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::CatalogRule,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@rule"), [3]),
+            ExpectedValue(json!({
+                "entity": "catalog_rule",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@rule": [3]
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_store_scoped_cms_entity() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Cms,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@page"), [2])
+            .with_data(ChangeAggregateKey::KeyAndScopeInt("@page", 1), [2]),
+            ExpectedValue(json!({
+                "entity": "cms",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@page": [2]
+                },
+                "scoped": {
+                    "@page": {"1": [2]}
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_customer_entity() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Customer,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@address"), [7]),
+            ExpectedValue(json!({
+                "entity": "customer",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@address": [7]
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_order_and_quote_entities() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Order,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@status"), [10]),
+            ExpectedValue(json!({
+                "entity": "order",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@status": [10]
+                }
+            }))
+        );
+
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Quote,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@item"), [5]),
+            ExpectedValue(json!({
+                "entity": "quote",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@item": [5]
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_config_paths_with_values() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Config,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(
+                ChangeAggregateKey::Key("@path"),
+                ["general/locale/code", "web/secure/base_url"]
+            )
+            .with_data(
+                ChangeAggregateKey::KeyAndScopeInt("stores", 1),
+                ["general/locale/code", "web/secure/base_url"]
+            )
+            .with_data(
+                ChangeAggregateKey::PathAndScopeInt("general/locale/code".into(), "stores", 1),
+                ChangeAggregateValue::Change(Some("en_US".into()), Some("nl_NL".into()))
+            )
+            .with_data(
+                ChangeAggregateKey::PathAndScopeInt("web/secure/base_url".into(), "stores", 1),
+                ChangeAggregateValue::Change(None, Some("https://b/".into()))
+            ),
+            ExpectedValue(json!({
+                "entity": "config",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@path": ["general/locale/code", "web/secure/base_url"]
+                },
+                "scoped": {
+                    "stores": {"1": ["general/locale/code", "web/secure/base_url"]}
+                },
+                "value": {
+                    "stores": {
+                        "1": {
+                            "general/locale/code": {"old": "en_US", "new": "nl_NL"},
+                            "web/secure/base_url": {"old": null, "new": "https://b/"}
+                        }
+                    }
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_store_entity() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Store,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@store"), [3])
+            .with_data(ChangeAggregateKey::KeyAndScopeInt("@store", 1), [3]),
+            ExpectedValue(json!({
+                "entity": "store",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@store": [3]
+                },
+                "scoped": {
+                    "@store": {"1": [3]}
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_eav_entity() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Eav,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@option"), [5, 6]),
+            ExpectedValue(json!({
+                "entity": "eav",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "global": {
+                    "@option": [5, 6]
+                }
+            }))
+        );
+    }
+
     #[tokio::test]
     async fn outputs_events_with_string_key() {
         output_test!(
@@ -221,4 +475,106 @@ mod tests {
             }))
         );
     }
+
+    #[tokio::test]
+    async fn outputs_events_with_store_scoped_attribute_map() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Product,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Attribute(1), [4, 5])
+            .with_data(ChangeAggregateKey::AttributeAndScopeInt(1, 0), [4])
+            .with_data(ChangeAggregateKey::AttributeAndScopeInt(1, 2), [5]),
+            ExpectedValue(json!({
+                "entity": "product",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "attribute": {
+                    "1": [4, 5],
+                },
+                "scoped_attribute": {
+                    "1": {
+                        "0": [4],
+                        "2": [5]
+                    }
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_attribute_codes_with_ids() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Product,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::AttributeCode("name".into(), 73), [4, 5])
+            .with_data(
+                ChangeAggregateKey::AttributeCodeAndScopeInt("name".into(), 73, 1),
+                [5]
+            ),
+            ExpectedValue(json!({
+                "entity": "product",
+                "metadata": {"timestamp": 10, "file": "bin.0000", "position": 4},
+                "attribute": {
+                    "name": [4, 5],
+                },
+                "attribute_id": {
+                    "name": 73
+                },
+                "scoped_attribute": {
+                    "name": {
+                        "1": [5]
+                    }
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_mariadb_gtid_and_statement_in_metadata() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Product,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4))
+                    .with_gtid(Some(MariaDbGtid::new(0, 1, 15)))
+                    .with_statement(Some("UPDATE entity SET name = 'Product'".into())),
+            ),
+            ExpectedValue(json!({
+                "entity": "product",
+                "metadata": {
+                    "timestamp": 10,
+                    "file": "bin.0000",
+                    "position": 4,
+                    "gtid": "0-1-15",
+                    "statement": "UPDATE entity SET name = 'Product'"
+                }
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_tenant_in_metadata() {
+        output_test!(
+            JsonOutput,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Product,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4))
+                    .with_tenant(Some("store_eu".into())),
+            ),
+            ExpectedValue(json!({
+                "entity": "product",
+                "metadata": {
+                    "timestamp": 10,
+                    "file": "bin.0000",
+                    "position": 4,
+                    "tenant": "store_eu"
+                }
+            }))
+        );
+    }
 }
@@ -2,7 +2,7 @@ use crate::aggregate::*;
 use crate::error::Error;
 use crate::output::Output;
 use rmp::encode::buffer::ByteBuf;
-use rmp::encode::{write_array_len, write_str, write_uint, ValueWriteError};
+use rmp::encode::{write_array_len, write_nil, write_str, write_uint, ValueWriteError};
 use std::convert::Infallible;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
@@ -40,6 +40,14 @@ fn entity_to_byte(entity: ChangeAggregateEntity) -> u8 {
         ChangeAggregateEntity::Product => 1,
         ChangeAggregateEntity::Category => 2,
         ChangeAggregateEntity::Inventory => 3,
+        ChangeAggregateEntity::CatalogRule => 4,
+        ChangeAggregateEntity::Cms => 5,
+        ChangeAggregateEntity::Customer => 6,
+        ChangeAggregateEntity::Order => 7,
+        ChangeAggregateEntity::Quote => 8,
+        ChangeAggregateEntity::Config => 9,
+        ChangeAggregateEntity::Store => 10,
+        ChangeAggregateEntity::Eav => 11,
     }
 }
 
@@ -51,6 +59,12 @@ fn write_header(aggregate: &ChangeAggregate, buffer: &mut ByteBuf) -> Result<(),
         buffer,
         aggregate.metadata.binlog_position().position() as u64,
     )?;
+
+    match aggregate.metadata.tenant() {
+        Some(tenant) => write_str(buffer, tenant)?,
+        None => write_nil(buffer).map_err(|_| Error::OutputError)?,
+    }
+
     write_uint(buffer, aggregate.data.len() as u64)?;
     Ok(())
 }
@@ -75,6 +89,28 @@ fn write_data_key(buffer: &mut ByteBuf, key: ChangeAggregateKey) -> Result<(), E
             write_uint(buffer, 4)?;
             write_uint(buffer, attribute_id as u64)?;
         }
+        ChangeAggregateKey::AttributeCode(code, attribute_id) => {
+            write_uint(buffer, 6)?;
+            write_str(buffer, code.as_ref())?;
+            write_uint(buffer, attribute_id as u64)?;
+        }
+        ChangeAggregateKey::AttributeCodeAndScopeInt(code, attribute_id, scope) => {
+            write_uint(buffer, 7)?;
+            write_str(buffer, code.as_ref())?;
+            write_uint(buffer, attribute_id as u64)?;
+            write_uint(buffer, scope as u64)?;
+        }
+        ChangeAggregateKey::PathAndScopeInt(path, scope, scope_id) => {
+            write_uint(buffer, 8)?;
+            write_str(buffer, path.as_ref())?;
+            write_str(buffer, scope)?;
+            write_uint(buffer, scope_id as u64)?;
+        }
+        ChangeAggregateKey::AttributeAndScopeInt(attribute_id, scope) => {
+            write_uint(buffer, 5)?;
+            write_uint(buffer, attribute_id as u64)?;
+            write_uint(buffer, scope as u64)?;
+        }
     }
     Ok(())
 }
@@ -93,6 +129,15 @@ fn write_data_value(buffer: &mut ByteBuf, value: ChangeAggregateValue) -> Result
                 write_str(buffer, id.as_ref())?;
             }
         }
+        ChangeAggregateValue::Change(old, new) => {
+            write_array_len(buffer, 2)?;
+            for value in [old, new] {
+                match value {
+                    Some(value) => write_str(buffer, value.as_ref())?,
+                    None => write_nil(buffer).map_err(|_| Error::OutputError)?,
+                }
+            }
+        }
     }
     Ok(())
 }
@@ -126,7 +171,7 @@ mod tests {
                 ChangeAggregateEntity::Product,
                 EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
             ),
-            ExpectedValue::new(b"\x01\x0a\xa8bin.0000\x04\x00")
+            ExpectedValue::new(b"\x01\x0a\xa8bin.0000\x04\xc0\x00")
         );
     }
 
@@ -139,7 +184,117 @@ mod tests {
                 EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
             )
             .with_data(ChangeAggregateKey::Key("sku"), [1, 2, 3]),
-            ExpectedValue::new(b"\x01\x0a\xa8bin.0000\x04\x01\x01\xa3sku\x93\x01\x02\x03")
+            ExpectedValue::new(b"\x01\x0a\xa8bin.0000\x04\xc0\x01\x01\xa3sku\x93\x01\x02\x03")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_catalog_rule_entity() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::CatalogRule,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@rule"), [3]),
+            ExpectedValue::new(b"\x04\x0a\xa8bin.0000\x04\xc0\x01\x01\xa5@rule\x91\x03")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_cms_entity() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Cms,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::KeyAndScopeInt("@block", 1), [5]),
+            ExpectedValue::new(b"\x05\x0a\xa8bin.0000\x04\xc0\x01\x02\xa6@block\x01\x91\x05")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_customer_entity() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Customer,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@created"), [3]),
+            ExpectedValue::new(b"\x06\x0a\xa8bin.0000\x04\xc0\x01\x01\xa8@created\x91\x03")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_order_entity() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Order,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@state"), [3]),
+            ExpectedValue::new(b"\x07\x0a\xa8bin.0000\x04\xc0\x01\x01\xa6@state\x91\x03")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_config_value_change() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Config,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(
+                ChangeAggregateKey::PathAndScopeInt("a/b/c".into(), "stores", 1),
+                ChangeAggregateValue::Change(None, Some("1".into()))
+            ),
+            ExpectedValue::new(
+                b"\x09\x0a\xa8bin.0000\x04\xc0\x01\x08\xa5a/b/c\xa6stores\x01\x92\xc0\xa11"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_store_entity() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Store,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@website"), [1]),
+            ExpectedValue::new(b"\x0a\x0a\xa8bin.0000\x04\xc0\x01\x01\xa8@website\x91\x01")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_eav_entity() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Eav,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::Key("@option"), [5]),
+            ExpectedValue::new(b"\x0b\x0a\xa8bin.0000\x04\xc0\x01\x01\xa7@option\x91\x05")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_tenant_in_place_of_nil_before_key_count() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Product,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4))
+                    .with_tenant(Some("eu".into())),
+            )
+            .with_data(ChangeAggregateKey::Key("@created"), [5]),
+            ExpectedValue::new(b"\x01\x0a\xa8bin.0000\x04\xa2eu\x01\x01\xa8@created\x91\x05")
         );
     }
 
@@ -155,7 +310,9 @@ mod tests {
                 ChangeAggregateKey::KeyAndScopeInt("websites", 10),
                 [1, 2, 3]
             ),
-            ExpectedValue::new(b"\x01\x0a\xa8bin.0000\x04\x01\x02\xa8websites\x0a\x93\x01\x02\x03")
+            ExpectedValue::new(
+                b"\x01\x0a\xa8bin.0000\x04\xc0\x01\x02\xa8websites\x0a\x93\x01\x02\x03"
+            )
         );
     }
 
@@ -172,7 +329,7 @@ mod tests {
                 [1, 2, 3]
             ),
             ExpectedValue::new(
-                b"\x01\x0a\xa8bin.0000\x04\x01\x03\xa8websites\xa7default\x93\x01\x02\x03"
+                b"\x01\x0a\xa8bin.0000\x04\xc0\x01\x03\xa8websites\xa7default\x93\x01\x02\x03"
             )
         );
     }
@@ -188,7 +345,39 @@ mod tests {
             .with_data(ChangeAggregateKey::Attribute(1), [4, 5, 6])
             .with_data(ChangeAggregateKey::Attribute(2), [1, 2, 3]),
             ExpectedValue::new(
-                b"\x01\x0a\xa8bin.0000\x04\x02\x04\x01\x93\x04\x05\x06\x04\x02\x93\x01\x02\x03"
+                b"\x01\x0a\xa8bin.0000\x04\xc0\x02\x04\x01\x93\x04\x05\x06\x04\x02\x93\x01\x02\x03"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_events_with_store_scoped_attributes() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Product,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::AttributeAndScopeInt(1, 2), [4, 5]),
+            ExpectedValue::new(b"\x01\x0a\xa8bin.0000\x04\xc0\x01\x05\x01\x02\x92\x04\x05")
+        );
+    }
+
+    #[tokio::test]
+    async fn outputs_events_with_attribute_codes() {
+        output_test!(
+            MessagePack,
+            ChangeAggregate::new(
+                ChangeAggregateEntity::Product,
+                EventMetadata::new(10, BinlogPosition::new("bin.0000", 4)),
+            )
+            .with_data(ChangeAggregateKey::AttributeCode("name".into(), 73), [4])
+            .with_data(
+                ChangeAggregateKey::AttributeCodeAndScopeInt("name".into(), 73, 1),
+                [4]
+            ),
+            ExpectedValue::new(
+                b"\x01\x0a\xa8bin.0000\x04\xc0\x02\x06\xa4name\x49\x91\x04\x07\xa4name\x49\x01\x91\x04"
             )
         );
     }
@@ -204,7 +393,7 @@ mod tests {
             .with_data(ChangeAggregateKey::Attribute(1), ["sku1"])
             .with_data(ChangeAggregateKey::Attribute(2), ["sku2"]),
             ExpectedValue::new(
-                b"\x01\x0a\xa8bin.0000\x04\x02\x04\x01\x91\xa4sku1\x04\x02\x91\xa4sku2"
+                b"\x01\x0a\xa8bin.0000\x04\xc0\x02\x04\x01\x91\xa4sku1\x04\x02\x91\xa4sku2"
             )
         );
     }
@@ -1,20 +1,80 @@
 use crate::database::Database;
 use crate::error::Error;
+use crate::metrics::Metrics;
 use crate::replication::binary_table::BinaryTable;
+use crate::replication::mariadb::{
+    compressed_rows_event_type, parse_annotate_rows, parse_gtid, parse_rows_event,
+    uncompress_rows_event, ANNOTATE_ROWS_EVENT, GTID_EVENT,
+};
 use crate::replication::{
-    BinaryRowIter, BinlogPosition, Event, EventMetadata, EventObserver, UpdateRowEvent,
+    BinaryRowIter, BinlogPosition, BinlogRowImage, Event, EventMetadata, EventObserver,
+    MariaDbGtid, UpdateRowEvent,
+};
+use crate::schema::{
+    row_version_entity, table_name_with_prefix, table_name_without_prefix, AttributeCodes,
+    MediaGalleryIndex, ParentIndex, RelationIndex, RowIdResolver, SchemaInformation, StoreIndex,
 };
-use crate::schema::{table_name_without_prefix, SchemaInformation};
+use mysql_async::{DriverError, Error as MySQLError};
 use mysql_common::binlog::consts::EventType;
-use mysql_common::binlog::events::{Event as BinLogEvent, EventData, RotateEvent, TableMapEvent};
+use mysql_common::binlog::events::{
+    Event as BinLogEvent, EventData, RotateEvent, RowsEventData, TableMapEvent,
+};
 use mysql_common::io::ParseBuf;
+use mysql_common::value::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
 use tokio_stream::StreamExt;
+use tracing::warn;
 
 pub struct ReplicationClient<D, T> {
     database: Database,
     database_name: D,
     table_prefix: T,
+    include_statement: bool,
+    metrics: Metrics,
+    reconnect_delay: Option<Duration>,
+    attribute_codes: Option<AttributeCodes>,
+    row_ids: Option<RowIdResolver>,
+    relations: Option<RelationIndex>,
+    parents: Option<ParentIndex>,
+    stores: Option<StoreIndex>,
+    media_gallery: Option<MediaGalleryIndex>,
+    tenant: Option<Arc<str>>,
+}
+
+/// Database of a tenant streamed over shared binlog connection
+struct TenantStream<'a, D, T, O> {
+    client: &'a ReplicationClient<D, T>,
+    observer: &'a O,
+    table_schema: SchemaInformation<'static>,
+}
+
+/// Tenant index, table name without prefix and column types by table id from `TABLE_MAP_EVENT`
+type BinaryTables = HashMap<u64, (usize, String, BinaryTable)>;
+
+/// Position of the tenant that owns the table by database name and the longest matching table prefix
+fn table_tenant<'a>(
+    tenants: impl IntoIterator<Item = (&'a str, &'a str)>,
+    database_name: &str,
+    table_name: &str,
+) -> Option<usize> {
+    tenants
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (database, prefix))| {
+            *database == database_name && table_name.starts_with(prefix)
+        })
+        .max_by_key(|(_, (_, prefix))| prefix.len())
+        .map(|(tenant, _)| tenant)
+}
+
+fn is_connection_error(error: &MySQLError) -> bool {
+    matches!(
+        error,
+        MySQLError::Io(_) | MySQLError::Driver(DriverError::ConnectionClosed)
+    )
 }
 
 impl<D, T> ReplicationClient<D, T>
@@ -27,16 +87,168 @@ where
             database,
             database_name,
             table_prefix,
+            include_statement: false,
+            metrics: Metrics::default(),
+            reconnect_delay: None,
+            attribute_codes: None,
+            row_ids: None,
+            relations: None,
+            parents: None,
+            stores: None,
+            media_gallery: None,
+            tenant: None,
+        }
+    }
+
+    /// Shares metrics and replication lag state that are updated on each event and heartbeat
+    pub fn with_metrics(self, metrics: Metrics) -> Self {
+        Self { metrics, ..self }
+    }
+
+    /// Re-establishes binlog stream after connection loss instead of returning an error
+    pub fn with_reconnect(self, delay: Duration) -> Self {
+        Self {
+            reconnect_delay: Some(delay),
+            ..self
+        }
+    }
+
+    /// Attaches SQL statement from MariaDB `ANNOTATE_ROWS_EVENT` to event metadata
+    pub fn with_statement(self, include_statement: bool) -> Self {
+        Self {
+            include_statement,
+            ..self
+        }
+    }
+
+    /// Loads attribute codes on start and keeps them up to date with `eav_attribute` changes
+    pub fn with_attribute_codes(self, attribute_codes: AttributeCodes) -> Self {
+        Self {
+            attribute_codes: Some(attribute_codes),
+            ..self
+        }
+    }
+
+    /// Maintains product entity ids of content staging row versions from binlog and lookups
+    pub fn with_row_id_resolver(self, row_ids: RowIdResolver) -> Self {
+        Self {
+            row_ids: Some(row_ids),
+            ..self
+        }
+    }
+
+    /// Maintains owners of product option and super attribute rows from binlog and lookups
+    pub fn with_relation_index(self, relations: RelationIndex) -> Self {
+        Self {
+            relations: Some(relations),
+            ..self
+        }
+    }
+
+    /// Loads composite product links on start and keeps them up to date with link table changes
+    pub fn with_parent_index(self, parents: ParentIndex) -> Self {
+        Self {
+            parents: Some(parents),
+            ..self
+        }
+    }
+
+    /// Loads store views on start and keeps them up to date with store table changes
+    pub fn with_store_index(self, stores: StoreIndex) -> Self {
+        Self {
+            stores: Some(stores),
+            ..self
+        }
+    }
+
+    /// Loads links of gallery images to products on start and keeps them up to date with link table changes
+    pub fn with_media_gallery_index(self, media_gallery: MediaGalleryIndex) -> Self {
+        Self {
+            media_gallery: Some(media_gallery),
+            ..self
+        }
+    }
+
+    /// Tags metadata of events from this database with tenant name
+    pub fn with_tenant(self, tenant: Option<impl Into<Arc<str>>>) -> Self {
+        Self {
+            tenant: tenant.map(Into::into),
+            ..self
         }
     }
 
     pub async fn process(
         &self,
         observer: impl EventObserver,
-        mut position: BinlogPosition,
+        position: BinlogPosition,
     ) -> Result<(), Error> {
-        let mut stream = self.database.binlog_stream(&position).await?;
-        let mut binary_tables: HashMap<u64, (String, BinaryTable)> = HashMap::new();
+        self.process_tenants(observer, Vec::new(), position).await
+    }
+
+    /// Processes events of this database and databases of other tenants from the same binlog stream
+    ///
+    /// Connection, metrics and reconnect settings of this client are used for the stream,
+    /// while each tenant keeps its own table prefix, schema, indexes and observer.
+    pub async fn process_tenants<O>(
+        &self,
+        observer: O,
+        tenants: Vec<(Self, O)>,
+        position: BinlogPosition,
+    ) -> Result<(), Error>
+    where
+        O: EventObserver,
+    {
+        let mut streams = vec![TenantStream {
+            client: self,
+            observer: &observer,
+            table_schema: self.populate().await?,
+        }];
+
+        for (client, observer) in &tenants {
+            streams.push(TenantStream {
+                client,
+                observer,
+                table_schema: client.populate().await?,
+            });
+        }
+
+        let row_image = self.database.binlog_row_image().await?;
+
+        if row_image.is_lossy() {
+            warn!(
+                "binlog_row_image is set to {row_image}, columns missing in row images are resolved \
+                by primary key lookup, values of deleted rows cannot be restored"
+            );
+        }
+
+        let mut resume_position = position;
+
+        loop {
+            let result = self
+                .process_stream(&streams, row_image, &mut resume_position)
+                .await;
+
+            self.metrics.set_connected(false);
+
+            match (result, self.reconnect_delay) {
+                (Err(Error::MySQLError(error)), Some(delay)) if is_connection_error(&error) => {
+                    warn!(
+                        "Binlog stream is interrupted, reconnecting from {}:{} in {}s: {error}",
+                        resume_position.file(),
+                        resume_position.position(),
+                        delay.as_secs()
+                    );
+
+                    self.metrics.record_reconnect();
+                    sleep(delay).await;
+                }
+                (result, _) => return result,
+            }
+        }
+    }
+
+    /// Loads table schema of the database and populates enabled indexes
+    async fn populate(&self) -> Result<SchemaInformation<'static>, Error> {
         let mut table_schema = SchemaInformation::default();
         table_schema
             .populate(
@@ -46,28 +258,122 @@ where
             )
             .await?;
 
+        if let Some(attribute_codes) = &self.attribute_codes {
+            attribute_codes
+                .populate(
+                    &mut self.database.acquire_connection().await?,
+                    &self.database_name,
+                    &self.table_prefix,
+                )
+                .await?;
+        }
+
+        if let Some(row_ids) = &self.row_ids {
+            if table_schema.has_content_staging() {
+                row_ids
+                    .populate(
+                        &mut self.database.acquire_connection().await?,
+                        &self.database_name,
+                        &self.table_prefix,
+                    )
+                    .await?;
+            }
+        }
+
+        if let Some(parents) = &self.parents {
+            parents
+                .populate(
+                    &mut self.database.acquire_connection().await?,
+                    &self.database_name,
+                    &self.table_prefix,
+                )
+                .await?;
+        }
+
+        if let Some(stores) = &self.stores {
+            stores
+                .populate(
+                    &mut self.database.acquire_connection().await?,
+                    &self.database_name,
+                    &self.table_prefix,
+                )
+                .await?;
+        }
+
+        if let Some(media_gallery) = &self.media_gallery {
+            media_gallery
+                .populate(
+                    &mut self.database.acquire_connection().await?,
+                    &self.database_name,
+                    &self.table_prefix,
+                )
+                .await?;
+        }
+
+        Ok(table_schema)
+    }
+
+    /// Reads binlog stream until it ends
+    ///
+    /// Resume position is moved only on transaction boundaries,
+    /// so after reconnect events of partially processed transaction are read again
+    async fn process_stream<O>(
+        &self,
+        streams: &[TenantStream<'_, D, T, O>],
+        row_image: BinlogRowImage,
+        resume_position: &mut BinlogPosition,
+    ) -> Result<(), Error>
+    where
+        O: EventObserver,
+    {
+        let mut stream = self.database.binlog_stream(resume_position).await?;
+        let mut binary_tables = BinaryTables::new();
+        let mut position = resume_position.clone();
+
+        self.metrics.set_connected(true);
+
+        let mut gtid: Option<MariaDbGtid> = None;
+        let mut statement: Option<Arc<str>> = None;
+
         while let Some(event) = stream.next().await {
             match event {
                 Ok(event) => {
                     position = position.with_position(event.header().log_pos());
+
+                    if event.header().timestamp() > 0 {
+                        self.metrics
+                            .lag()
+                            .observe_event(event.header().timestamp() as u64, &position);
+                    }
+
+                    let metadata =
+                        EventMetadata::new(event.header().timestamp() as usize, position.clone())
+                            .with_gtid(gtid)
+                            .with_statement(statement.clone());
+
                     match event.header().event_type() {
                         Ok(
                             EventType::DELETE_ROWS_EVENT
                             | EventType::WRITE_ROWS_EVENT
                             | EventType::UPDATE_ROWS_EVENT
-                            | EventType::PARTIAL_UPDATE_ROWS_EVENT,
+                            | EventType::PARTIAL_UPDATE_ROWS_EVENT
+                            | EventType::DELETE_ROWS_EVENT_V1
+                            | EventType::WRITE_ROWS_EVENT_V1
+                            | EventType::UPDATE_ROWS_EVENT_V1,
                         ) => {
-                            self.process_rows_event(
-                                &observer,
-                                &binary_tables,
-                                &table_schema,
-                                event,
-                                &position,
-                            )
-                            .await?;
+                            if let Some(EventData::RowsEvent(rows_event)) = event.read_data()? {
+                                Self::dispatch_rows_event(
+                                    streams,
+                                    &binary_tables,
+                                    rows_event,
+                                    &metadata,
+                                    row_image,
+                                )
+                                .await?;
+                            }
                         }
                         Ok(EventType::TABLE_MAP_EVENT) => {
-                            self.process_table_event(&mut binary_tables, event).await?
+                            Self::process_table_event(streams, &mut binary_tables, event)?
                         }
                         Ok(EventType::ROTATE_EVENT) => {
                             let rotate_event: RotateEvent = match event.read_event() {
@@ -77,9 +383,45 @@ where
 
                             position = position
                                 .with_file(rotate_event.name())
-                                .with_position(rotate_event.position() as u32)
+                                .with_position(rotate_event.position() as u32);
+                            *resume_position = position.clone();
                         }
-                        _ => continue,
+                        Ok(EventType::HEARTBEAT_EVENT) => {
+                            position = position.with_file(String::from_utf8_lossy(event.data()));
+                            self.metrics.lag().observe_heartbeat(&position);
+                            *resume_position = position.clone();
+                        }
+                        Ok(EventType::XID_EVENT | EventType::QUERY_EVENT) => {
+                            *resume_position = position.clone();
+                        }
+                        Ok(_) => continue,
+                        Err(_) => match event.header().event_type_raw() {
+                            GTID_EVENT => {
+                                gtid = Some(parse_gtid(event.header().server_id(), event.data())?);
+                                statement = None;
+                            }
+                            ANNOTATE_ROWS_EVENT if self.include_statement => {
+                                statement = Some(parse_annotate_rows(event.data()));
+                            }
+                            event_type => {
+                                let event_type = match compressed_rows_event_type(event_type) {
+                                    Some(event_type) => event_type,
+                                    None => continue,
+                                };
+
+                                let data =
+                                    uncompress_rows_event(event_type, event.data(), event.fde())?;
+
+                                Self::dispatch_rows_event(
+                                    streams,
+                                    &binary_tables,
+                                    parse_rows_event(event_type, &data, event.fde())?,
+                                    &metadata,
+                                    row_image,
+                                )
+                                .await?;
+                            }
+                        },
                     }
                 }
                 Err(error) => return Err(Error::from(error)),
@@ -89,70 +431,236 @@ where
         Ok(())
     }
 
-    async fn process_rows_event(
+    /// Passes rows event to the tenant that owns the table
+    async fn dispatch_rows_event<O>(
+        streams: &[TenantStream<'_, D, T, O>],
+        binary_tables: &BinaryTables,
+        event: RowsEventData<'_>,
+        metadata: &EventMetadata,
+        row_image: BinlogRowImage,
+    ) -> Result<(), Error>
+    where
+        O: EventObserver,
+    {
+        let (tenant, table_name, binary_table) = match binary_tables.get(&event.table_id()) {
+            Some(binary_table) => binary_table,
+            None => return Ok(()),
+        };
+
+        streams[*tenant]
+            .process_rows_event(table_name, binary_table, event, metadata, row_image)
+            .await
+    }
+
+    /// Checks if rows of the table are used by enabled indexes
+    fn is_indexed_table(&self, table_name: &str) -> bool {
+        self.attribute_codes
+            .as_ref()
+            .is_some_and(|index| index.is_indexed_table(table_name))
+            || self
+                .row_ids
+                .as_ref()
+                .is_some_and(|index| index.is_indexed_table(table_name))
+            || self
+                .relations
+                .as_ref()
+                .is_some_and(|index| index.is_indexed_table(table_name))
+            || self
+                .parents
+                .as_ref()
+                .is_some_and(|index| index.is_indexed_table(table_name))
+            || self
+                .stores
+                .as_ref()
+                .is_some_and(|index| index.is_indexed_table(table_name))
+            || self
+                .media_gallery
+                .as_ref()
+                .is_some_and(|index| index.is_indexed_table(table_name))
+    }
+
+    /// Fills columns missing in row images of one rows event with values looked up in a single query
+    async fn resolve_missing_columns(
         &self,
-        observer: &impl EventObserver,
-        binary_tables: &HashMap<u64, (String, BinaryTable)>,
+        events: &mut [Event],
+        table_name: &str,
         table_info: &SchemaInformation<'_>,
-        binlog_event: BinLogEvent,
-        position: &BinlogPosition,
     ) -> Result<(), Error> {
-        match binlog_event.read_data() {
-            Ok(Some(EventData::RowsEvent(event))) => {
-                let (table_name, binary_table) = match binary_tables.get(&event.table_id()) {
-                    Some(binary_table) => binary_table,
-                    None => return Ok(()),
-                };
+        let primary_key = match table_info.generated_primary_key(table_name) {
+            Some(primary_key) => primary_key,
+            None => return Ok(()),
+        };
 
-                for row in BinaryRowIter::new(&event, binary_table, ParseBuf(event.rows_data())) {
-                    let event = match row {
-                        Err(error) => return Err(Error::from(error)),
-                        Ok((None, Some(after))) => Event::InsertRow(after),
-                        Ok((Some(before), None)) => Event::DeleteRow(before),
-                        Ok((Some(before), Some(after))) => {
-                            Event::UpdateRow(UpdateRowEvent::new(before, after))
-                        }
-                        _ => continue,
-                    };
+        let schema = table_info.table_schema(table_name);
+        let mut incomplete = Vec::new();
 
-                    observer
-                        .process_event(&event, &table_info.table_schema(&table_name))
-                        .await?
-                }
+        for (index, event) in events.iter().enumerate() {
+            if event.is_complete() {
+                continue;
+            }
+
+            let value: Value = match match event {
+                Event::InsertRow(row) | Event::DeleteRow(row) => row.parse(primary_key, &schema),
+                Event::UpdateRow(row) => row.parse(primary_key, &schema),
+            } {
+                Ok(value) => value,
+                Err(Error::ColumnNotFound(_)) => continue,
+                Err(error) => return Err(error),
+            };
+
+            incomplete.push((index, value));
+        }
+
+        if incomplete.is_empty() {
+            return Ok(());
+        }
+
+        let rows = self
+            .database
+            .lookup_rows(
+                self.database_name.as_ref(),
+                &table_name_with_prefix(table_name, self.table_prefix.as_ref()),
+                primary_key,
+                incomplete.iter().map(|(_, value)| value.clone()).collect(),
+            )
+            .await?;
+
+        // Values of binlog and query result can differ in type, so keys are compared as SQL literals
+        let mut rows_by_key = HashMap::new();
+        for row in rows {
+            let key: Value = row.parse(primary_key, &schema)?;
+            rows_by_key.insert(key.as_sql(false), row);
+        }
+
+        for (index, value) in incomplete {
+            if let Some(row) = rows_by_key.get(&value.as_sql(false)) {
+                events[index].fill_missing(row);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registers row versions of staged entities and looks up the ones created before binlog position
+    async fn resolve_row_ids(
+        &self,
+        row_ids: &RowIdResolver,
+        event: &Event,
+        table_name: &str,
+        table_info: &SchemaInformation<'_>,
+    ) -> Result<(), Error> {
+        if !table_info.has_content_staging() {
+            return Ok(());
+        }
+
+        let schema = table_info.table_schema(table_name);
+        row_ids.apply_event(event, &schema)?;
 
-                let metadata = EventMetadata::new(
-                    binlog_event.header().timestamp() as usize,
-                    position.clone(),
-                );
+        let (entity_table, id) = match row_version_entity(&schema) {
+            Some(entity) => entity,
+            None => return Ok(()),
+        };
 
-                observer.process_metadata(&metadata).await?;
+        let entity_schema = table_info.table_schema(entity_table);
 
-                Ok(())
+        for row_id in row_ids.unresolved(event, &schema)? {
+            let row = self
+                .database
+                .lookup_row(
+                    self.database_name.as_ref(),
+                    &table_name_with_prefix(entity_table, self.table_prefix.as_ref()),
+                    "row_id",
+                    Value::from(row_id),
+                )
+                .await?;
+
+            if let Some(row) = row {
+                row_ids.insert_entity(entity_table, row_id, row.parse(id, &entity_schema)?);
             }
-            Err(error) => Err(Error::from(error)),
-            _ => Ok(()),
         }
+
+        Ok(())
     }
 
-    async fn process_table_event(
+    /// Registers owners of related rows and looks up the ones that were created before binlog position
+    async fn resolve_relations(
         &self,
-        binary_tables: &mut HashMap<u64, (String, BinaryTable)>,
+        relations: &RelationIndex,
+        event: &Event,
+        table_name: &str,
+        table_info: &SchemaInformation<'_>,
+    ) -> Result<(), Error> {
+        let schema = table_info.table_schema(table_name);
+        relations.apply_event(event, &schema)?;
+
+        let (column, relation) = match relations.table_relation(table_name) {
+            Some(relation) => relation,
+            None => return Ok(()),
+        };
+
+        for id in event.map_owners(|row| row.parse(column, &schema))? {
+            let mut missing = relations.missing(relation, id);
+
+            while let Some((relation, id)) = missing {
+                let row = self
+                    .database
+                    .lookup_row(
+                        self.database_name.as_ref(),
+                        &table_name_with_prefix(relation.table, self.table_prefix.as_ref()),
+                        relation.id,
+                        Value::from(id),
+                    )
+                    .await?;
+
+                let owner_schema = table_info.table_schema(relation.table);
+                let owner = match row {
+                    Some(row)
+                        if relation.is_related(|column| row.parse(column, &owner_schema))? =>
+                    {
+                        row.parse(relation.owner, &owner_schema)?
+                    }
+                    _ => break,
+                };
+
+                relations.insert(relation, id, owner);
+                missing = relations.missing(relation, id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registers table of the tenant with the same database and table prefix, other tables are skipped
+    fn process_table_event<O>(
+        streams: &[TenantStream<'_, D, T, O>],
+        binary_tables: &mut BinaryTables,
         binlog_event: BinLogEvent,
     ) -> Result<(), Error> {
         let table_map_event: TableMapEvent = binlog_event.read_event().map_err(Error::Io)?;
 
-        if table_map_event
-            .database_name()
-            .ne(self.database_name.as_ref())
-        {
-            return Ok(());
-        }
+        let tenant = match table_tenant(
+            streams.iter().map(|stream| {
+                (
+                    stream.client.database_name.as_ref(),
+                    stream.client.table_prefix.as_ref(),
+                )
+            }),
+            &table_map_event.database_name(),
+            &table_map_event.table_name(),
+        ) {
+            Some(tenant) => tenant,
+            None => return Ok(()),
+        };
 
         binary_tables
             .entry(table_map_event.table_id())
             .or_insert_with(|| {
                 (
-                    table_name_without_prefix(table_map_event.table_name(), &self.table_prefix),
+                    tenant,
+                    table_name_without_prefix(
+                        table_map_event.table_name(),
+                        &streams[tenant].client.table_prefix,
+                    ),
                     BinaryTable::from_table_map_event(&table_map_event),
                 )
             });
@@ -160,3 +668,120 @@ where
         Ok(())
     }
 }
+
+impl<D, T, O> TenantStream<'_, D, T, O>
+where
+    D: AsRef<str>,
+    T: AsRef<str>,
+    O: EventObserver,
+{
+    async fn process_rows_event(
+        &self,
+        table_name: &str,
+        binary_table: &BinaryTable,
+        event: RowsEventData<'_>,
+        metadata: &EventMetadata,
+        row_image: BinlogRowImage,
+    ) -> Result<(), Error> {
+        let client = self.client;
+        let table_info = &self.table_schema;
+        let mut events = Vec::new();
+
+        for row in BinaryRowIter::new(&event, binary_table, ParseBuf(event.rows_data())) {
+            events.push(match row {
+                Err(error) => return Err(Error::from(error)),
+                Ok((None, Some(after))) => Event::InsertRow(after),
+                Ok((Some(before), None)) => Event::DeleteRow(before),
+                Ok((Some(before), Some(after))) => {
+                    Event::UpdateRow(UpdateRowEvent::new(before, after))
+                }
+                _ => continue,
+            });
+
+            client.metrics.record_event(table_name);
+        }
+
+        if row_image.is_lossy()
+            && (self.observer.is_observed_table(table_name) || client.is_indexed_table(table_name))
+        {
+            client
+                .resolve_missing_columns(&mut events, table_name, table_info)
+                .await?;
+        }
+
+        for event in events {
+            let schema = table_info.table_schema(table_name);
+
+            if let Some(attribute_codes) = &client.attribute_codes {
+                attribute_codes.apply_event(&event, &schema)?;
+            }
+
+            if let Some(parents) = &client.parents {
+                parents.apply_event(&event, &schema)?;
+            }
+
+            if let Some(stores) = &client.stores {
+                stores.apply_event(&event, &schema)?;
+            }
+
+            if let Some(media_gallery) = &client.media_gallery {
+                media_gallery.apply_event(&event, &schema)?;
+            }
+
+            if let Some(row_ids) = &client.row_ids {
+                client
+                    .resolve_row_ids(row_ids, &event, table_name, table_info)
+                    .await?;
+            }
+
+            if let Some(relations) = &client.relations {
+                client
+                    .resolve_relations(relations, &event, table_name, table_info)
+                    .await?;
+            }
+
+            self.observer.process_event(&event, &schema).await?
+        }
+
+        self.observer
+            .process_metadata(&metadata.clone().with_tenant(client.tenant.clone()))
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error as StdIoError, ErrorKind};
+
+    #[test]
+    fn reconnects_only_after_lost_connection() {
+        assert_eq!(
+            vec![
+                is_connection_error(&MySQLError::Io(
+                    StdIoError::from(ErrorKind::ConnectionReset).into()
+                )),
+                is_connection_error(&MySQLError::Driver(DriverError::ConnectionClosed)),
+                is_connection_error(&MySQLError::Driver(DriverError::PoolDisconnected)),
+            ],
+            vec![true, true, false]
+        );
+    }
+
+    #[test]
+    fn dispatches_table_to_tenant_with_longest_matching_prefix() {
+        let tenants = [("magento", ""), ("magento", "eu_"), ("magento_us", "")];
+
+        assert_eq!(
+            vec![
+                table_tenant(tenants, "magento", "catalog_product_entity"),
+                table_tenant(tenants, "magento", "eu_catalog_product_entity"),
+                table_tenant(tenants, "magento_us", "catalog_product_entity"),
+                table_tenant(tenants, "magento_nl", "catalog_product_entity"),
+            ],
+            vec![Some(0), Some(1), Some(2), None]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -52,10 +53,49 @@ impl BinlogPosition {
     }
 }
 
+/// Global transaction identifier as written by MariaDB in `GTID_EVENT`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MariaDbGtid {
+    domain_id: u32,
+    server_id: u32,
+    sequence: u64,
+}
+
+impl MariaDbGtid {
+    pub fn new(domain_id: u32, server_id: u32, sequence: u64) -> Self {
+        Self {
+            domain_id,
+            server_id,
+            sequence,
+        }
+    }
+
+    pub fn domain_id(&self) -> u32 {
+        self.domain_id
+    }
+
+    pub fn server_id(&self) -> u32 {
+        self.server_id
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+}
+
+impl Display for MariaDbGtid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}-{}", self.domain_id, self.server_id, self.sequence)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct EventMetadata {
     timestamp: usize,
     binlog_position: BinlogPosition,
+    gtid: Option<MariaDbGtid>,
+    statement: Option<Arc<str>>,
+    tenant: Option<Arc<str>>,
 }
 
 impl EventMetadata {
@@ -63,9 +103,26 @@ impl EventMetadata {
         Self {
             timestamp,
             binlog_position,
+            gtid: None,
+            statement: None,
+            tenant: None,
         }
     }
 
+    pub fn with_gtid(self, gtid: Option<MariaDbGtid>) -> Self {
+        Self { gtid, ..self }
+    }
+
+    /// Attaches SQL statement that produced the rows event (MariaDB `ANNOTATE_ROWS_EVENT`)
+    pub fn with_statement(self, statement: Option<Arc<str>>) -> Self {
+        Self { statement, ..self }
+    }
+
+    /// Attaches name of the tenant the database of the event belongs to
+    pub fn with_tenant(self, tenant: Option<Arc<str>>) -> Self {
+        Self { tenant, ..self }
+    }
+
     pub fn timestamp(&self) -> usize {
         self.timestamp
     }
@@ -73,14 +130,23 @@ impl EventMetadata {
     pub fn binlog_position(&self) -> &BinlogPosition {
         &self.binlog_position
     }
+
+    pub fn gtid(&self) -> Option<&MariaDbGtid> {
+        self.gtid.as_ref()
+    }
+
+    pub fn statement(&self) -> Option<&str> {
+        self.statement.as_deref()
+    }
+
+    pub fn tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::replication::event::meta::BinlogPosition;
-    
-    
-    
+    use crate::replication::event::meta::{BinlogPosition, EventMetadata, MariaDbGtid};
 
     #[test]
     fn creates_binlog_file_from_string() {
@@ -108,4 +174,30 @@ mod tests {
 
         assert_eq!(log.file(), "binlogfile.0001");
     }
+
+    #[test]
+    fn formats_mariadb_gtid_as_domain_server_and_sequence() {
+        assert_eq!(MariaDbGtid::new(0, 1, 1050).to_string(), "0-1-1050");
+    }
+
+    #[test]
+    fn carries_gtid_and_statement_in_metadata() {
+        let metadata = EventMetadata::new(10, BinlogPosition::new("binlogfile.0000", 390))
+            .with_gtid(Some(MariaDbGtid::new(1, 2, 3)))
+            .with_statement(Some("UPDATE entity SET name = 'Name'".into()));
+
+        assert_eq!(metadata.gtid(), Some(&MariaDbGtid::new(1, 2, 3)));
+        assert_eq!(
+            metadata.statement(),
+            Some("UPDATE entity SET name = 'Name'")
+        );
+    }
+
+    #[test]
+    fn carries_tenant_in_metadata() {
+        let metadata = EventMetadata::new(10, BinlogPosition::new("binlogfile.0000", 390))
+            .with_tenant(Some("store_eu".into()));
+
+        assert_eq!(metadata.tenant(), Some("store_eu"));
+    }
 }
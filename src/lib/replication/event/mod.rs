@@ -1,9 +1,13 @@
+use crate::error::Error;
 use crate::replication::BinaryRow;
+use crate::schema::TableSchema;
+use mysql_common::value::convert::FromValue;
+use smallvec::{smallvec, SmallVec};
 
 mod meta;
 mod update_event;
 
-pub use meta::{BinlogPosition, EventMetadata};
+pub use meta::{BinlogPosition, EventMetadata, MariaDbGtid};
 pub use update_event::UpdateRowEvent;
 
 #[derive(Clone, PartialEq, Debug)]
@@ -12,3 +16,60 @@ pub enum Event {
     UpdateRow(UpdateRowEvent),
     DeleteRow(BinaryRow),
 }
+
+/// Row of the event as it was before or after the change
+///
+/// Used for mapping owner of the row for every image it appears in
+pub enum EventRow<'a> {
+    Row(&'a BinaryRow),
+    Before(&'a UpdateRowEvent),
+    After(&'a UpdateRowEvent),
+}
+
+impl EventRow<'_> {
+    pub fn parse<T>(&self, column: impl AsRef<str>, schema: &impl TableSchema) -> Result<T, Error>
+    where
+        T: FromValue,
+    {
+        match self {
+            Self::Row(row) => row.parse(column, schema),
+            Self::Before(row) => row.parse(column, schema),
+            Self::After(row) => row.parse_changed(column, schema),
+        }
+    }
+}
+
+impl Event {
+    /// Maps row of the event into its owner
+    ///
+    /// For update both the owner before and after change are returned
+    /// when update moved the row to another owner
+    pub fn map_owners<T, F>(&self, map: F) -> Result<SmallVec<[T; 2]>, Error>
+    where
+        T: PartialEq,
+        F: Fn(EventRow) -> Result<T, Error>,
+    {
+        match self {
+            Event::InsertRow(row) | Event::DeleteRow(row) => {
+                Ok(smallvec![map(EventRow::Row(row))?])
+            }
+            Event::UpdateRow(row) => row.map_owners(map),
+        }
+    }
+
+    /// Checks if all column values are present in row images of the event
+    pub fn is_complete(&self) -> bool {
+        match self {
+            Event::InsertRow(row) | Event::DeleteRow(row) => row.is_complete(),
+            Event::UpdateRow(row) => row.is_complete(),
+        }
+    }
+
+    /// Takes values for columns missing in row images from another row
+    pub fn fill_missing(&mut self, source: &BinaryRow) {
+        match self {
+            Event::InsertRow(row) | Event::DeleteRow(row) => row.fill_missing(source),
+            Event::UpdateRow(row) => row.fill_missing(source),
+        }
+    }
+}
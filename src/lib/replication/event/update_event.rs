@@ -1,8 +1,9 @@
 use crate::error::Error;
-use crate::replication::BinaryRow;
+use crate::replication::{BinaryRow, EventRow};
 use crate::schema::TableSchema;
 
 use mysql_common::value::convert::FromValue;
+use smallvec::{smallvec, SmallVec};
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct UpdateRowEvent {
@@ -23,6 +24,10 @@ impl UpdateRowEvent {
         self.before.parse(column, schema)
     }
 
+    /// Parses value from after image
+    ///
+    /// Falls back to before image when column is omitted from after image,
+    /// as it happens for unchanged columns with `MINIMAL` or `NOBLOB` row image
     pub fn parse_changed<T>(
         &self,
         column: impl AsRef<str>,
@@ -31,9 +36,35 @@ impl UpdateRowEvent {
     where
         T: FromValue,
     {
-        self.after.parse(column, schema)
+        match self.after.parse(&column, schema) {
+            Err(Error::ColumnNotFound(_)) => self.before.parse(column, schema),
+            result => result,
+        }
+    }
+
+    /// Maps before and after image of the row into its owner
+    ///
+    /// Owner from after image is returned only when it differs from the one in before image,
+    /// so update that moves the row to another owner reports both of them
+    pub fn map_owners<T, F>(&self, map: F) -> Result<SmallVec<[T; 2]>, Error>
+    where
+        T: PartialEq,
+        F: Fn(EventRow) -> Result<T, Error>,
+    {
+        let before = map(EventRow::Before(self))?;
+        let after = map(EventRow::After(self))?;
+
+        Ok(if before == after {
+            smallvec![before]
+        } else {
+            smallvec![before, after]
+        })
     }
 
+    /// Checks if column value is different between before and after image
+    ///
+    /// Column that is present only in after image is reported as changed,
+    /// while column missing in after image is considered unchanged
     pub fn is_changed_column(&self, column: impl AsRef<str>, schema: &impl TableSchema) -> bool {
         let position = match schema.column_position(&column) {
             Some(position) => position,
@@ -42,17 +73,30 @@ impl UpdateRowEvent {
 
         match (self.before.get(position), self.after.get(position)) {
             (Some(left), Some(right)) => left.ne(right),
-            (None, None) => false,
-            _ => true,
+            (None, Some(_)) => true,
+            _ => false,
         }
     }
+
+    /// Checks if every column has value in before or after image
+    pub fn is_complete(&self) -> bool {
+        (0..self.before.len().max(self.after.len()))
+            .all(|index| self.before.get(index).is_some() || self.after.get(index).is_some())
+    }
+
+    /// Takes values for columns missing in both images from another row into before image
+    pub fn fill_missing(&mut self, source: &BinaryRow) {
+        let after = &self.after;
+
+        self.before
+            .fill_missing_when(source, |index| after.get(index).is_none())
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use crate::replication::UpdateRowEvent;
-    
 
     #[test]
     fn takes_value_from_before_column() {
@@ -99,4 +143,98 @@ mod tests {
             vec![false, true]
         );
     }
+
+    #[test]
+    fn reports_changes_for_minimal_row_image() {
+        let updates = UpdateRowEvent::new(
+            binlog_row!(1, binlog_none!(), binlog_none!()),
+            binlog_row!(binlog_none!(), "Name After", binlog_none!()),
+        );
+
+        let schema = test_table!("entity", ["entity_id", "name", "sku"]);
+
+        assert_eq!(
+            vec![
+                updates.is_changed_column("entity_id", &schema),
+                updates.is_changed_column("name", &schema),
+                updates.is_changed_column("sku", &schema)
+            ],
+            vec![false, true, false]
+        );
+    }
+
+    #[test]
+    fn takes_changed_value_from_before_image_when_omitted_in_after_image() {
+        let updates = UpdateRowEvent::new(
+            binlog_row!(1, binlog_none!()),
+            binlog_row!(binlog_none!(), "Name After"),
+        );
+
+        let schema = test_table!("entity", ["entity_id", "name"]);
+
+        assert_eq!(
+            updates
+                .parse_changed::<usize>("entity_id", &schema)
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn maps_single_owner_when_identity_is_not_changed() {
+        let updates =
+            UpdateRowEvent::new(binlog_row!(1, "Name Before"), binlog_row!(1, "Name After"));
+
+        let schema = test_table!("entity", ["entity_id", "name"]);
+
+        assert_eq!(
+            updates
+                .map_owners(|row| row.parse::<usize>("entity_id", &schema))
+                .unwrap()
+                .into_vec(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn maps_before_and_after_owner_when_row_is_moved() {
+        let updates = UpdateRowEvent::new(
+            binlog_row!(1, 4, "Name"),
+            binlog_row!(2, binlog_none!(), binlog_none!()),
+        );
+
+        let schema = test_table!("entity", ["entity_id", "store_id", "name"]);
+
+        assert_eq!(
+            updates
+                .map_owners(|row| Ok((
+                    row.parse::<usize>("entity_id", &schema)?,
+                    row.parse::<usize>("store_id", &schema)?
+                )))
+                .unwrap()
+                .into_vec(),
+            vec![(1, 4), (2, 4)]
+        );
+    }
+
+    #[test]
+    fn fills_before_image_only_for_columns_missing_in_both_images() {
+        let mut updates = UpdateRowEvent::new(
+            binlog_row!(1, binlog_none!(), binlog_none!()),
+            binlog_row!(binlog_none!(), "Name After", binlog_none!()),
+        );
+
+        assert!(!updates.is_complete());
+
+        updates.fill_missing(&binlog_row!(1, "Name After", "sku1"));
+
+        assert_eq!(
+            updates,
+            UpdateRowEvent::new(
+                binlog_row!(1, binlog_none!(), "sku1"),
+                binlog_row!(binlog_none!(), "Name After", binlog_none!()),
+            )
+        );
+        assert!(updates.is_complete());
+    }
 }
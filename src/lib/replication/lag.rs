@@ -0,0 +1,172 @@
+use crate::replication::BinlogPosition;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Replication lag of the changelog behind the source server
+///
+/// Timestamps of events are compared with the wall clock when they are read,
+/// while positions of events and heartbeats are kept next to the current position
+/// of the source server, so both time and binlog distance can be reported as metrics.
+#[derive(Clone, Default)]
+pub struct ReplicationLag {
+    state: Arc<ReplicationLagState>,
+}
+
+#[derive(Default)]
+struct ReplicationLagState {
+    seconds_behind: AtomicU64,
+    last_event_at: AtomicU64,
+    position: Mutex<Option<BinlogPosition>>,
+    master_position: Mutex<Option<BinlogPosition>>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn file_sequence(position: &BinlogPosition) -> Option<u64> {
+    let (_, sequence) = position.file().rsplit_once('.')?;
+
+    sequence.parse().ok()
+}
+
+impl ReplicationLag {
+    /// Records event read from binlog with timestamp of its execution on the source server
+    pub fn observe_event(&self, timestamp: u64, position: &BinlogPosition) {
+        self.observe_event_at(timestamp, position, now())
+    }
+
+    pub(crate) fn observe_event_at(&self, timestamp: u64, position: &BinlogPosition, now: u64) {
+        self.state
+            .seconds_behind
+            .store(now.saturating_sub(timestamp), Ordering::Relaxed);
+        self.state.last_event_at.store(now, Ordering::Relaxed);
+        self.observe_position(position);
+    }
+
+    /// Records heartbeat, which is sent by the source server only when there are no events left to read
+    pub fn observe_heartbeat(&self, position: &BinlogPosition) {
+        self.state.seconds_behind.store(0, Ordering::Relaxed);
+        self.state.last_event_at.store(now(), Ordering::Relaxed);
+        self.observe_position(position);
+    }
+
+    /// Records current position reported by `SHOW MASTER STATUS`
+    pub fn observe_master_position(&self, position: BinlogPosition) {
+        if let Ok(mut master_position) = self.state.master_position.lock() {
+            *master_position = Some(position);
+        }
+    }
+
+    fn observe_position(&self, position: &BinlogPosition) {
+        if let Ok(mut current) = self.state.position.lock() {
+            *current = Some(position.clone());
+        }
+    }
+
+    /// Difference between wall clock and timestamp of the last event at the moment it was read
+    pub fn seconds_behind(&self) -> u64 {
+        self.state.seconds_behind.load(Ordering::Relaxed)
+    }
+
+    /// Unix timestamp when the last event or heartbeat was read
+    pub fn last_event_at(&self) -> Option<u64> {
+        match self.state.last_event_at.load(Ordering::Relaxed) {
+            0 => None,
+            value => Some(value),
+        }
+    }
+
+    pub fn position(&self) -> Option<BinlogPosition> {
+        self.state.position.lock().ok()?.clone()
+    }
+
+    pub fn master_position(&self) -> Option<BinlogPosition> {
+        self.state.master_position.lock().ok()?.clone()
+    }
+
+    /// Number of binlog files between current position and source server position
+    pub fn files_behind(&self) -> Option<u64> {
+        let (position, master_position) = (self.position()?, self.master_position()?);
+
+        Some(file_sequence(&master_position)?.saturating_sub(file_sequence(&position)?))
+    }
+
+    /// Number of bytes between current position and source server position,
+    /// available only when both positions are in the same binlog file
+    pub fn bytes_behind(&self) -> Option<u64> {
+        let (position, master_position) = (self.position()?, self.master_position()?);
+
+        if position.file() != master_position.file() {
+            return None;
+        }
+
+        Some(
+            master_position
+                .position()
+                .saturating_sub(position.position()) as u64,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculates_seconds_behind_from_event_timestamp() {
+        let lag = ReplicationLag::default();
+
+        lag.observe_event_at(1000, &BinlogPosition::new("bin.000001", 4), 1015);
+
+        assert_eq!(lag.seconds_behind(), 15);
+        assert_eq!(lag.last_event_at(), Some(1015));
+    }
+
+    #[test]
+    fn resets_seconds_behind_on_heartbeat() {
+        let lag = ReplicationLag::default();
+
+        lag.observe_event_at(1000, &BinlogPosition::new("bin.000001", 4), 1015);
+        lag.observe_heartbeat(&BinlogPosition::new("bin.000001", 100));
+
+        assert_eq!(lag.seconds_behind(), 0);
+        assert_eq!(lag.position(), Some(BinlogPosition::new("bin.000001", 100)));
+    }
+
+    #[test]
+    fn calculates_bytes_behind_within_the_same_file() {
+        let lag = ReplicationLag::default();
+
+        lag.observe_heartbeat(&BinlogPosition::new("bin.000002", 100));
+        lag.observe_master_position(BinlogPosition::new("bin.000002", 1500));
+
+        assert_eq!(lag.bytes_behind(), Some(1400));
+        assert_eq!(lag.files_behind(), Some(0));
+    }
+
+    #[test]
+    fn calculates_files_behind_for_different_files() {
+        let lag = ReplicationLag::default();
+
+        lag.observe_heartbeat(&BinlogPosition::new("bin.000002", 100));
+        lag.observe_master_position(BinlogPosition::new("bin.000005", 40));
+
+        assert_eq!(lag.bytes_behind(), None);
+        assert_eq!(lag.files_behind(), Some(3));
+    }
+
+    #[test]
+    fn has_no_position_lag_until_master_position_is_known() {
+        let lag = ReplicationLag::default();
+
+        lag.observe_heartbeat(&BinlogPosition::new("bin.000002", 100));
+
+        assert_eq!(lag.bytes_behind(), None);
+        assert_eq!(lag.files_behind(), None);
+    }
+}
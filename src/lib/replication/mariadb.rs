@@ -0,0 +1,225 @@
+//! MariaDB specific binlog events that are not recognized by `mysql_common`
+use crate::replication::MariaDbGtid;
+
+use flate2::read::ZlibDecoder;
+use mysql_common::binlog::consts::EventType;
+use mysql_common::binlog::events::{BinlogEventHeader, FormatDescriptionEvent, RowsEventData};
+use mysql_common::binlog::BinlogCtx;
+use mysql_common::io::ParseBuf;
+use mysql_common::packets::BinlogDumpFlags;
+use std::io::{Error as IoError, ErrorKind, Read, Result as IoResult};
+use std::sync::Arc;
+
+pub(crate) const ANNOTATE_ROWS_EVENT: u8 = 0xa0;
+pub(crate) const GTID_EVENT: u8 = 0xa2;
+pub(crate) const WRITE_ROWS_COMPRESSED_EVENT_V1: u8 = 0xa6;
+pub(crate) const UPDATE_ROWS_COMPRESSED_EVENT_V1: u8 = 0xa7;
+pub(crate) const DELETE_ROWS_COMPRESSED_EVENT_V1: u8 = 0xa8;
+pub(crate) const WRITE_ROWS_COMPRESSED_EVENT: u8 = 0xa9;
+pub(crate) const UPDATE_ROWS_COMPRESSED_EVENT: u8 = 0xaa;
+pub(crate) const DELETE_ROWS_COMPRESSED_EVENT: u8 = 0xab;
+
+/// Session variable value that makes MariaDB send its own GTID events instead of `BEGIN` queries
+pub const MARIADB_SLAVE_CAPABILITY: u8 = 4;
+
+/// Binlog dump flag that makes MariaDB send `ANNOTATE_ROWS_EVENT` before rows events
+///
+/// MariaDB uses the bit MySQL assigns to `BINLOG_THROUGH_POSITION`,
+/// which has no meaning for binlog dump by file and position.
+pub const BINLOG_SEND_ANNOTATE_ROWS_EVENT: BinlogDumpFlags =
+    BinlogDumpFlags::BINLOG_THROUGH_POSITION;
+
+/// Maps compressed rows event into the event type it has after decompression
+pub(crate) fn compressed_rows_event_type(event_type: u8) -> Option<EventType> {
+    match event_type {
+        WRITE_ROWS_COMPRESSED_EVENT_V1 => Some(EventType::WRITE_ROWS_EVENT_V1),
+        UPDATE_ROWS_COMPRESSED_EVENT_V1 => Some(EventType::UPDATE_ROWS_EVENT_V1),
+        DELETE_ROWS_COMPRESSED_EVENT_V1 => Some(EventType::DELETE_ROWS_EVENT_V1),
+        WRITE_ROWS_COMPRESSED_EVENT => Some(EventType::WRITE_ROWS_EVENT),
+        UPDATE_ROWS_COMPRESSED_EVENT => Some(EventType::UPDATE_ROWS_EVENT),
+        DELETE_ROWS_COMPRESSED_EVENT => Some(EventType::DELETE_ROWS_EVENT),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_gtid(server_id: u32, data: &[u8]) -> IoResult<MariaDbGtid> {
+    if data.len() < 12 {
+        return Err(IoError::new(
+            ErrorKind::UnexpectedEof,
+            "MariaDB GTID event is too short",
+        ));
+    }
+
+    let mut sequence = [0; 8];
+    let mut domain_id = [0; 4];
+    sequence.copy_from_slice(&data[0..8]);
+    domain_id.copy_from_slice(&data[8..12]);
+
+    Ok(MariaDbGtid::new(
+        u32::from_le_bytes(domain_id),
+        server_id,
+        u64::from_le_bytes(sequence),
+    ))
+}
+
+pub(crate) fn parse_annotate_rows(data: &[u8]) -> Arc<str> {
+    String::from_utf8_lossy(data).into()
+}
+
+pub(crate) fn parse_rows_event<'a>(
+    event_type: EventType,
+    data: &'a [u8],
+    fde: &'a FormatDescriptionEvent<'a>,
+) -> IoResult<RowsEventData<'a>> {
+    let ctx = BinlogCtx::new(BinlogEventHeader::LEN + data.len(), fde);
+    let mut buffer = ParseBuf(data);
+
+    Ok(match event_type {
+        EventType::WRITE_ROWS_EVENT_V1 => RowsEventData::WriteRowsEventV1(buffer.parse(ctx)?),
+        EventType::UPDATE_ROWS_EVENT_V1 => RowsEventData::UpdateRowsEventV1(buffer.parse(ctx)?),
+        EventType::DELETE_ROWS_EVENT_V1 => RowsEventData::DeleteRowsEventV1(buffer.parse(ctx)?),
+        EventType::WRITE_ROWS_EVENT => RowsEventData::WriteRowsEvent(buffer.parse(ctx)?),
+        EventType::UPDATE_ROWS_EVENT => RowsEventData::UpdateRowsEvent(buffer.parse(ctx)?),
+        EventType::DELETE_ROWS_EVENT => RowsEventData::DeleteRowsEvent(buffer.parse(ctx)?),
+        _ => {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                format!("{event_type:?} is not a rows event"),
+            ))
+        }
+    })
+}
+
+/// Restores uncompressed rows event data from MariaDB compressed rows event
+///
+/// Only rows image is compressed by MariaDB, table id, flags and column bitmaps are kept as is.
+pub(crate) fn uncompress_rows_event(
+    event_type: EventType,
+    data: &[u8],
+    fde: &FormatDescriptionEvent,
+) -> IoResult<Vec<u8>> {
+    let compressed_length = parse_rows_event(event_type, data, fde)?.rows_data().len();
+    let header_length = data.len() - compressed_length;
+
+    let mut uncompressed = Vec::from(&data[..header_length]);
+    uncompress_rows_data(&data[header_length..], &mut uncompressed)?;
+
+    Ok(uncompressed)
+}
+
+fn uncompress_rows_data(compressed: &[u8], output: &mut Vec<u8>) -> IoResult<()> {
+    let (header, body) = compressed
+        .split_first()
+        .ok_or_else(|| IoError::new(ErrorKind::UnexpectedEof, "Compressed rows data is empty"))?;
+
+    let length_bytes = (header & 0x07) as usize;
+
+    if length_bytes == 0 || length_bytes > 4 || body.len() < length_bytes {
+        return Err(IoError::new(
+            ErrorKind::InvalidData,
+            "Compressed rows data has invalid length header",
+        ));
+    }
+
+    let length = body[..length_bytes]
+        .iter()
+        .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+
+    output.reserve(length);
+    ZlibDecoder::new(&body[length_bytes..]).read_to_end(output)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::test_fixture::Fixture;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use mysql_common::binlog::consts::BinlogVersion;
+    use std::io::Write;
+
+    fn compress_rows(rows: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(rows).unwrap();
+
+        let mut compressed = vec![0x80 | 4];
+        compressed.extend_from_slice(&(rows.len() as u32).to_be_bytes());
+        compressed.extend(encoder.finish().unwrap());
+        compressed
+    }
+
+    #[test]
+    fn maps_compressed_rows_events_to_uncompressed_types() {
+        assert_eq!(
+            vec![
+                compressed_rows_event_type(WRITE_ROWS_COMPRESSED_EVENT_V1),
+                compressed_rows_event_type(UPDATE_ROWS_COMPRESSED_EVENT),
+                compressed_rows_event_type(DELETE_ROWS_COMPRESSED_EVENT),
+                compressed_rows_event_type(GTID_EVENT),
+            ],
+            vec![
+                Some(EventType::WRITE_ROWS_EVENT_V1),
+                Some(EventType::UPDATE_ROWS_EVENT),
+                Some(EventType::DELETE_ROWS_EVENT),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_gtid_from_event_data() {
+        let data = [0x1a, 0x04, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0];
+
+        assert_eq!(parse_gtid(7, &data).unwrap(), MariaDbGtid::new(2, 7, 1050));
+    }
+
+    #[test]
+    fn errors_on_truncated_gtid_event() {
+        assert_eq!(
+            parse_gtid(7, &[0x1a, 0x04]).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn reads_annotated_statement() {
+        assert_eq!(
+            parse_annotate_rows(b"INSERT INTO entity VALUES (1)").as_ref(),
+            "INSERT INTO entity VALUES (1)"
+        );
+    }
+
+    #[test]
+    fn restores_compressed_rows_event() {
+        let (event_type, data) = Fixture::row_event_data("write_entity");
+        let fde = FormatDescriptionEvent::new(BinlogVersion::Version4);
+
+        let rows_length = parse_rows_event(event_type, data, &fde)
+            .unwrap()
+            .rows_data()
+            .len();
+
+        let header_length = data.len() - rows_length;
+        let mut compressed = Vec::from(&data[..header_length]);
+        compressed.extend(compress_rows(&data[header_length..]));
+
+        assert_eq!(
+            uncompress_rows_event(event_type, &compressed, &fde).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn errors_on_invalid_compressed_rows_header() {
+        let mut output = Vec::new();
+
+        assert_eq!(
+            uncompress_rows_data(&[0x80, 1, 2], &mut output)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
@@ -1,6 +1,9 @@
 mod binary_table;
 mod client;
+mod lag;
+mod mariadb;
 mod row;
+mod row_image;
 mod rows;
 
 mod event;
@@ -11,6 +14,9 @@ pub(crate) mod test_fixture;
 
 pub use client::ReplicationClient;
 pub use event::*;
+pub use lag::ReplicationLag;
+pub use mariadb::{BINLOG_SEND_ANNOTATE_ROWS_EVENT, MARIADB_SLAVE_CAPABILITY};
 pub use observer::*;
 pub use row::BinaryRow;
+pub use row_image::BinlogRowImage;
 pub use rows::BinaryRowIter;
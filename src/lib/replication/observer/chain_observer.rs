@@ -34,6 +34,10 @@ where
         self.right.process_metadata(metadata).await?;
         Ok(())
     }
+
+    fn is_observed_table(&self, table_name: &str) -> bool {
+        self.left.is_observed_table(table_name) || self.right.is_observed_table(table_name)
+    }
 }
 
 #[cfg(test)]
@@ -50,6 +50,10 @@ where
     async fn process_metadata(&self, metadata: &EventMetadata) -> Result<(), Error> {
         self.observer.process_metadata(metadata).await
     }
+
+    fn is_observed_table(&self, table_name: &str) -> bool {
+        self.observer.is_observed_table(table_name)
+    }
 }
 
 #[cfg(test)]
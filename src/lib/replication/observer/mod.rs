@@ -14,6 +14,13 @@ pub trait EventObserver: Sized {
     async fn process_event(&self, event: &Event, table: &impl TableSchema) -> Result<(), Error>;
 
     async fn process_metadata(&self, metadata: &EventMetadata) -> Result<(), Error>;
+
+    /// Checks if events of the table are used by observer
+    ///
+    /// Rows of tables that are not observed are not looked up when binlog row image is lossy.
+    fn is_observed_table(&self, _table_name: &str) -> bool {
+        true
+    }
 }
 
 pub trait EventObserverExt: Sized {
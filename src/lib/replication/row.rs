@@ -117,6 +117,39 @@ impl BinaryRow {
         }
     }
 
+    /// Number of columns in the row including the ones missing in row image
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Checks if all column values are present in row image
+    ///
+    /// With `binlog_row_image` set to `MINIMAL` or `NOBLOB` server omits some of the columns
+    pub fn is_complete(&self) -> bool {
+        self.values.iter().all(Option::is_some)
+    }
+
+    /// Takes values of columns missing in row image from another row
+    pub fn fill_missing(&mut self, source: &BinaryRow) {
+        self.fill_missing_when(source, |_| true)
+    }
+
+    pub(crate) fn fill_missing_when(&mut self, source: &BinaryRow, filter: impl Fn(usize) -> bool) {
+        if self.values.len() < source.values.len() {
+            self.values.resize(source.values.len(), None);
+        }
+
+        for (index, value) in self.values.iter_mut().enumerate() {
+            if value.is_none() && filter(index) {
+                *value = source.get(index).cloned();
+            }
+        }
+    }
+
     pub fn parse<T>(&self, column: impl AsRef<str>, schema: &impl TableSchema) -> Result<T, Error>
     where
         T: FromValue,
@@ -213,6 +246,26 @@ mod tests {
         )
     }
 
+    #[test]
+    fn reports_row_as_complete_only_when_all_values_are_present() {
+        assert_eq!(
+            vec![
+                binlog_row!(1, "Name 1", "sku1", "10.00").is_complete(),
+                binlog_row!(1, binlog_none!(), "sku1", "10.00").is_complete(),
+            ],
+            vec![true, false]
+        );
+    }
+
+    #[test]
+    fn fills_only_missing_values_from_another_row() {
+        let mut row = binlog_row!(1, binlog_none!(), "sku1", binlog_none!());
+
+        row.fill_missing(&binlog_row!(2, "Name 2", "sku2", "20.00"));
+
+        assert_eq!(row, binlog_row!(1, "Name 2", "sku1", "20.00"));
+    }
+
     fn table_schema() -> TestTableSchema {
         test_table!("entity", "entity_id", ["entity_id", "name", "sku", "price"])
     }
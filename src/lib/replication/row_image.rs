@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+
+/// Value of `binlog_row_image` server variable
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BinlogRowImage {
+    #[default]
+    Full,
+    Minimal,
+    NoBlob,
+}
+
+impl BinlogRowImage {
+    /// Parses server variable value, unknown values are treated as full image
+    pub fn from_variable(value: impl AsRef<str>) -> Self {
+        match value.as_ref().to_ascii_uppercase().as_str() {
+            "MINIMAL" => Self::Minimal,
+            "NOBLOB" => Self::NoBlob,
+            _ => Self::Full,
+        }
+    }
+
+    /// Row image omits columns that might be required for identifying changed entity
+    pub fn is_lossy(&self) -> bool {
+        !matches!(self, Self::Full)
+    }
+}
+
+impl Display for BinlogRowImage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Full => "FULL",
+            Self::Minimal => "MINIMAL",
+            Self::NoBlob => "NOBLOB",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinlogRowImage;
+
+    #[test]
+    fn parses_server_variable_value() {
+        assert_eq!(
+            vec![
+                BinlogRowImage::from_variable("FULL"),
+                BinlogRowImage::from_variable("minimal"),
+                BinlogRowImage::from_variable("NOBLOB"),
+                BinlogRowImage::from_variable("unknown"),
+            ],
+            vec![
+                BinlogRowImage::Full,
+                BinlogRowImage::Minimal,
+                BinlogRowImage::NoBlob,
+                BinlogRowImage::Full
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_only_partial_images_as_lossy() {
+        assert_eq!(
+            vec![
+                BinlogRowImage::Full.is_lossy(),
+                BinlogRowImage::Minimal.is_lossy(),
+                BinlogRowImage::NoBlob.is_lossy(),
+            ],
+            vec![false, true, true]
+        );
+    }
+}
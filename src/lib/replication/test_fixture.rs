@@ -150,6 +150,12 @@ impl Fixture {
     pub fn table_event(&self, name: &'static str) -> TableMapEvent<'_> {
         table_event(name, &self.fde)
     }
+
+    pub fn row_event_data(name: &'static str) -> (EventType, &'static [u8]) {
+        let (_, event_type, event_data) = TABLE_ROWS_EVENTS.get(name).unwrap();
+
+        (*event_type, event_data)
+    }
 }
 
 fn row_event<'a>(
@@ -0,0 +1,256 @@
+use crate::error::Error;
+use crate::replication::Event;
+use crate::schema::{table_name_with_prefix, TableSchema};
+use mysql_async::prelude::Queryable;
+use mysql_async::Conn;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// EAV attribute codes by attribute id
+///
+/// Loaded from `eav_attribute` and `eav_entity_type` on start and refreshed from their binlog changes,
+/// so changed attributes are reported by code, even for attributes created after the start
+#[derive(Clone, Default)]
+pub struct AttributeCodes {
+    state: Arc<RwLock<AttributeCodesState>>,
+}
+
+#[derive(Default)]
+struct AttributeCodesState {
+    entity_types: HashMap<usize, Arc<str>>,
+    attributes: HashMap<usize, (usize, Arc<str>)>,
+}
+
+impl AttributeCodes {
+    /// Loads entity types and attributes from database
+    pub async fn populate<T, P>(
+        &self,
+        connection: &mut Conn,
+        database_name: T,
+        table_prefix: P,
+    ) -> Result<(), Error>
+    where
+        T: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let database_name = database_name.as_ref();
+        let entity_type_table = table_name_with_prefix("eav_entity_type", table_prefix.as_ref());
+        let attribute_table = table_name_with_prefix("eav_attribute", table_prefix.as_ref());
+
+        let entity_types: Vec<(usize, String)> = connection
+            .query(format!(
+                "SELECT entity_type_id, entity_type_code FROM `{database_name}`.`{entity_type_table}`"
+            ))
+            .await?;
+
+        let attributes: Vec<(usize, usize, String)> = connection
+            .query(format!(
+                "SELECT attribute_id, entity_type_id, attribute_code FROM `{database_name}`.`{attribute_table}`"
+            ))
+            .await?;
+
+        let mut state = self.state.write().map_err(|_| Error::Synchronization)?;
+        state.entity_types.clear();
+        state.attributes.clear();
+
+        for (entity_type_id, code) in entity_types {
+            state.entity_types.insert(entity_type_id, code.into());
+        }
+
+        for (attribute_id, entity_type_id, code) in attributes {
+            state
+                .attributes
+                .insert(attribute_id, (entity_type_id, code.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Checks if attribute codes are refreshed from changes of the table
+    pub fn is_indexed_table(&self, table_name: &str) -> bool {
+        matches!(table_name, "eav_attribute" | "eav_entity_type")
+    }
+
+    /// Refreshes attribute codes from `eav_attribute` and `eav_entity_type` row changes
+    pub fn apply_event(&self, event: &Event, schema: &impl TableSchema) -> Result<(), Error> {
+        let (id_column, is_attribute) = match schema.table_name() {
+            "eav_attribute" => ("attribute_id", true),
+            "eav_entity_type" => ("entity_type_id", false),
+            _ => return Ok(()),
+        };
+
+        let mut state = self.state.write().map_err(|_| Error::Synchronization)?;
+
+        match (event, is_attribute) {
+            (Event::DeleteRow(row), true) => {
+                state.attributes.remove(&row.parse(id_column, schema)?);
+            }
+            (Event::DeleteRow(row), false) => {
+                state.entity_types.remove(&row.parse(id_column, schema)?);
+            }
+            (Event::InsertRow(row), true) => {
+                state.attributes.insert(
+                    row.parse(id_column, schema)?,
+                    (
+                        row.parse("entity_type_id", schema)?,
+                        row.parse::<String>("attribute_code", schema)?.into(),
+                    ),
+                );
+            }
+            (Event::InsertRow(row), false) => {
+                state.entity_types.insert(
+                    row.parse(id_column, schema)?,
+                    row.parse::<String>("entity_type_code", schema)?.into(),
+                );
+            }
+            (Event::UpdateRow(row), true) => {
+                state.attributes.remove(&row.parse(id_column, schema)?);
+                state.attributes.insert(
+                    row.parse_changed(id_column, schema)?,
+                    (
+                        row.parse_changed("entity_type_id", schema)?,
+                        row.parse_changed::<String>("attribute_code", schema)?
+                            .into(),
+                    ),
+                );
+            }
+            (Event::UpdateRow(row), false) => {
+                state.entity_types.remove(&row.parse(id_column, schema)?);
+                state.entity_types.insert(
+                    row.parse_changed(id_column, schema)?,
+                    row.parse_changed::<String>("entity_type_code", schema)?
+                        .into(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Code of the attribute, if it is known
+    pub fn code(&self, attribute_id: usize) -> Option<Arc<str>> {
+        let state = self.state.read().ok()?;
+
+        state
+            .attributes
+            .get(&attribute_id)
+            .map(|(_, code)| code.clone())
+    }
+
+    /// Code of the entity type attribute belongs to, like `catalog_product`
+    pub fn entity_type(&self, attribute_id: usize) -> Option<Arc<str>> {
+        let state = self.state.read().ok()?;
+        let (entity_type_id, _) = state.attributes.get(&attribute_id)?;
+
+        state.entity_types.get(entity_type_id).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state
+            .read()
+            .map(|state| state.attributes.is_empty())
+            .unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn attribute_codes() -> AttributeCodes {
+        let codes = AttributeCodes::default();
+
+        codes
+            .apply_event(
+                &Event::InsertRow(binlog_row!(4, "catalog_product")),
+                &test_table!("eav_entity_type", ["entity_type_id", "entity_type_code"]),
+            )
+            .unwrap();
+
+        for (attribute_id, code) in [(73, "name"), (77, "price")] {
+            codes
+                .apply_event(
+                    &Event::InsertRow(binlog_row!(attribute_id, 4, code)),
+                    &test_table!(
+                        "eav_attribute",
+                        ["attribute_id", "entity_type_id", "attribute_code"]
+                    ),
+                )
+                .unwrap();
+        }
+
+        codes
+    }
+
+    #[test]
+    fn resolves_inserted_attribute_codes() {
+        let codes = attribute_codes();
+
+        assert_eq!(
+            vec![codes.code(73), codes.code(77), codes.code(78)],
+            vec![Some("name".into()), Some("price".into()), None]
+        );
+    }
+
+    #[test]
+    fn resolves_entity_type_of_attribute() {
+        let codes = attribute_codes();
+
+        assert_eq!(codes.entity_type(73), Some("catalog_product".into()));
+    }
+
+    #[test]
+    fn refreshes_code_on_update() {
+        let codes = attribute_codes();
+
+        codes
+            .apply_event(
+                &Event::UpdateRow(UpdateRowEvent::new(
+                    binlog_row!(73, 4, "name"),
+                    binlog_row!(73, 4, "title"),
+                )),
+                &test_table!(
+                    "eav_attribute",
+                    ["attribute_id", "entity_type_id", "attribute_code"]
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(codes.code(73), Some("title".into()));
+    }
+
+    #[test]
+    fn forgets_deleted_attribute() {
+        let codes = attribute_codes();
+
+        codes
+            .apply_event(
+                &Event::DeleteRow(binlog_row!(77, 4, "price")),
+                &test_table!(
+                    "eav_attribute",
+                    ["attribute_id", "entity_type_id", "attribute_code"]
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(codes.code(77), None);
+    }
+
+    #[test]
+    fn ignores_other_tables() {
+        let codes = AttributeCodes::default();
+
+        codes
+            .apply_event(
+                &Event::InsertRow(binlog_row!(1, 4, "sku")),
+                &test_table!(
+                    "catalog_product_entity",
+                    ["entity_id", "attribute_set_id", "sku"]
+                ),
+            )
+            .unwrap();
+
+        assert!(codes.is_empty());
+    }
+}
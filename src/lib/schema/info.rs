@@ -1,5 +1,5 @@
 use crate::schema::info_table::InfoSchemaTable;
-use crate::schema::{table_name_without_prefix};
+use crate::schema::table_name_without_prefix;
 use mysql_async::prelude::Queryable;
 use mysql_async::{Conn, Error};
 use mysql_common::params::Params;
@@ -102,6 +102,22 @@ impl<'a> SchemaInformation<'a> {
         }
     }
 
+    /// Returns single auto_incremented column of the table, if there is one
+    pub fn generated_primary_key<T>(&self, table: T) -> Option<&str>
+    where
+        T: AsRef<str>,
+    {
+        self.generated_primary_key
+            .get(table.as_ref())
+            .map(AsRef::as_ref)
+    }
+
+    /// Checks if product tables are versioned by Adobe Commerce content staging
+    pub fn has_content_staging(&self) -> bool {
+        self.get_column_position("catalog_product_entity", "row_id")
+            .is_some()
+    }
+
     pub fn table_schema(&'a self, table_name: &'a str) -> InfoSchemaTable<'a> {
         InfoSchemaTable::new(self, table_name)
     }
@@ -160,4 +176,56 @@ mod tests {
             vec![false, true, true, false]
         );
     }
+
+    #[test]
+    fn detects_content_staging_by_row_id_in_product_table() {
+        let mut community = SchemaInformation::default();
+        community.populate_columns(
+            vec![("catalog_product_entity".into(), "entity_id".into(), 0, true)].into_iter(),
+        );
+
+        let mut commerce = SchemaInformation::default();
+        commerce.populate_columns(
+            vec![
+                ("catalog_product_entity".into(), "row_id".into(), 0, true),
+                (
+                    "catalog_product_entity".into(),
+                    "entity_id".into(),
+                    1,
+                    false,
+                ),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(
+            vec![
+                community.has_content_staging(),
+                commerce.has_content_staging()
+            ],
+            vec![false, true]
+        );
+    }
+
+    #[test]
+    fn returns_generated_primary_key_column_of_table() {
+        let mut schema = SchemaInformation::default();
+        schema.populate_columns(
+            vec![
+                ("entity".into(), "entity_id".into(), 0, true),
+                ("entity_int".into(), "value_id".into(), 0, true),
+                ("entity_link".into(), "entity_id".into(), 0, false),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(
+            vec![
+                schema.generated_primary_key("entity"),
+                schema.generated_primary_key("entity_int"),
+                schema.generated_primary_key("entity_link"),
+            ],
+            vec![Some("entity_id"), Some("value_id"), None]
+        );
+    }
 }
@@ -0,0 +1,220 @@
+use crate::error::Error;
+use crate::replication::Event;
+use crate::schema::{table_name_with_prefix, RowIdResolver, TableSchema};
+use mysql_async::prelude::Queryable;
+use mysql_async::Conn;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// Table linking gallery images to products in Magento 2
+const VALUE_TO_ENTITY_TABLE: &str = "catalog_product_entity_media_gallery_value_to_entity";
+
+/// Products of gallery images
+///
+/// In Magento 2 one image row can be linked to several products through
+/// `catalog_product_entity_media_gallery_value_to_entity`,
+/// so a changed file is reported for each product that uses it.
+/// Link table of Adobe Commerce holds row version of the product,
+/// which is resolved to its product when row id resolver is attached.
+#[derive(Clone, Default)]
+pub struct MediaGalleryIndex {
+    products: Arc<RwLock<HashMap<usize, HashSet<usize>>>>,
+    row_ids: Option<RowIdResolver>,
+}
+
+/// Column of link table that references the product, `row_id` in Adobe Commerce
+fn product_column(schema: &impl TableSchema) -> Option<&'static str> {
+    ["entity_id", "row_id"]
+        .into_iter()
+        .find(|column| schema.has_column(column))
+}
+
+impl MediaGalleryIndex {
+    pub fn with_row_id_resolver(self, row_ids: RowIdResolver) -> Self {
+        Self {
+            row_ids: Some(row_ids),
+            ..self
+        }
+    }
+
+    /// Loads existing links of images to products, when link table exists in database
+    pub async fn populate<T, P>(
+        &self,
+        connection: &mut Conn,
+        database_name: T,
+        table_prefix: P,
+    ) -> Result<(), Error>
+    where
+        T: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let database_name = database_name.as_ref();
+        let table_name = table_name_with_prefix(VALUE_TO_ENTITY_TABLE, table_prefix.as_ref());
+        let column: Option<String> = connection
+            .exec_first(
+                "SELECT COLUMN_NAME FROM information_schema.COLUMNS \
+                 WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? \
+                 AND COLUMN_NAME IN ('entity_id', 'row_id') ORDER BY COLUMN_NAME",
+                (database_name, table_name.as_ref()),
+            )
+            .await?;
+
+        let column = match column {
+            Some(column) => column,
+            None => return Ok(()),
+        };
+
+        let links: Vec<(usize, usize)> = connection
+            .query(format!(
+                "SELECT value_id, {column} FROM `{database_name}`.`{table_name}`"
+            ))
+            .await?;
+
+        for (value_id, entity_id) in links {
+            self.insert(value_id, entity_id);
+        }
+
+        Ok(())
+    }
+
+    pub fn insert(&self, value_id: usize, entity_id: usize) {
+        if let Ok(mut products) = self.products.write() {
+            products.entry(value_id).or_default().insert(entity_id);
+        }
+    }
+
+    fn remove(&self, value_id: usize, entity_id: usize) {
+        if let Ok(mut products) = self.products.write() {
+            if let Some(entities) = products.get_mut(&value_id) {
+                entities.remove(&entity_id);
+
+                if entities.is_empty() {
+                    products.remove(&value_id);
+                }
+            }
+        }
+    }
+
+    /// Product of the linked product column, resolved from row version with content staging
+    pub fn product(&self, linked_id: usize) -> Option<usize> {
+        match self
+            .row_ids
+            .as_ref()
+            .filter(|row_ids| row_ids.is_content_staging())
+        {
+            Some(row_ids) => row_ids.resolve(linked_id),
+            None => Some(linked_id),
+        }
+    }
+
+    /// Products the image is linked to, sorted by id
+    pub fn products(&self, value_id: usize) -> Vec<usize> {
+        let mut products: Vec<usize> = match self.products.read() {
+            Ok(products) => products
+                .get(&value_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|linked_id| self.product(*linked_id))
+                .collect(),
+            Err(_) => return Vec::new(),
+        };
+
+        products.sort();
+        products.dedup();
+        products
+    }
+
+    /// Checks if links are maintained from changes of the table
+    pub fn is_indexed_table(&self, table_name: &str) -> bool {
+        table_name == VALUE_TO_ENTITY_TABLE
+    }
+
+    /// Maintains links from changes in image to product link table
+    pub fn apply_event(&self, event: &Event, schema: &impl TableSchema) -> Result<(), Error> {
+        let column = match product_column(schema) {
+            Some(column) if schema.table_name() == VALUE_TO_ENTITY_TABLE => column,
+            _ => return Ok(()),
+        };
+
+        match event {
+            Event::InsertRow(row) => {
+                self.insert(row.parse("value_id", schema)?, row.parse(column, schema)?)
+            }
+            Event::DeleteRow(row) => {
+                self.remove(row.parse("value_id", schema)?, row.parse(column, schema)?)
+            }
+            Event::UpdateRow(row) => {
+                self.remove(row.parse("value_id", schema)?, row.parse(column, schema)?);
+                self.insert(
+                    row.parse_changed("value_id", schema)?,
+                    row.parse_changed(column, schema)?,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    #[test]
+    fn lists_products_of_shared_image() {
+        let index = MediaGalleryIndex::default();
+        index.insert(1, 20);
+        index.insert(1, 10);
+        index.insert(2, 30);
+
+        assert_eq!(
+            (index.products(1), index.products(2), index.products(3)),
+            (vec![10, 20], vec![30], vec![])
+        );
+    }
+
+    #[test]
+    fn maintains_links_from_link_table_changes() {
+        let index = MediaGalleryIndex::default();
+        let schema = test_table!(VALUE_TO_ENTITY_TABLE, ["value_id", "entity_id"]);
+
+        index
+            .apply_event(&Event::InsertRow(binlog_row!(1, 10)), &schema)
+            .unwrap();
+        index
+            .apply_event(&Event::InsertRow(binlog_row!(1, 11)), &schema)
+            .unwrap();
+        index
+            .apply_event(&Event::DeleteRow(binlog_row!(1, 10)), &schema)
+            .unwrap();
+        index
+            .apply_event(
+                &Event::UpdateRow(UpdateRowEvent::new(binlog_row!(1, 11), binlog_row!(2, 11))),
+                &schema,
+            )
+            .unwrap();
+
+        assert_eq!((index.products(1), index.products(2)), (vec![], vec![11]));
+    }
+
+    #[test]
+    fn resolves_product_row_versions_with_content_staging() {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert(100, 10);
+        row_ids.insert(101, 10);
+        row_ids.insert(110, 11);
+        row_ids.set_content_staging();
+
+        let index = MediaGalleryIndex::default().with_row_id_resolver(row_ids);
+        let schema = test_table!(VALUE_TO_ENTITY_TABLE, ["value_id", "row_id"]);
+
+        for row_id in [100, 101, 110] {
+            index
+                .apply_event(&Event::InsertRow(binlog_row!(1, row_id)), &schema)
+                .unwrap();
+        }
+
+        assert_eq!(index.products(1), vec![10, 11]);
+    }
+}
@@ -1,10 +1,24 @@
 //! Table schema for mapping of binary log rows into actual column names
+mod attribute;
 mod info;
 mod info_table;
+mod media_gallery;
+mod parent;
+mod relation;
+mod row_id;
+mod store;
 mod table_name;
 
+pub use attribute::AttributeCodes;
 pub use info::SchemaInformation;
-pub(crate) use table_name::table_name_without_prefix;
+pub use media_gallery::MediaGalleryIndex;
+pub use parent::ParentIndex;
+pub use relation::{
+    Relation, RelationIndex, PRODUCT_OPTION, PRODUCT_OPTION_TYPE, PRODUCT_SUPER_ATTRIBUTE,
+};
+pub use row_id::{is_row_id_table, is_row_version_relation_table, row_version_entity, RowIdResolver};
+pub use store::StoreIndex;
+pub(crate) use table_name::{table_name_with_prefix, table_name_without_prefix};
 
 /// Table schema provider
 ///
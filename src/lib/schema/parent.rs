@@ -0,0 +1,264 @@
+use crate::error::Error;
+use crate::replication::Event;
+use crate::schema::{table_name_with_prefix, RowIdResolver, TableSchema};
+use mysql_async::prelude::Queryable;
+use mysql_async::Conn;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// Tables with child to parent product links, as table, child and parent columns
+const PARENT_LINKS: [(&str, &str, &str); 3] = [
+    ("catalog_product_super_link", "product_id", "parent_id"),
+    (
+        "catalog_product_bundle_selection",
+        "product_id",
+        "parent_product_id",
+    ),
+    ("catalog_product_relation", "child_id", "parent_id"),
+];
+
+/// Parents of a child product with the link table they come from
+type ParentLinks = HashSet<(&'static str, usize)>;
+
+/// Parent products of composite product children
+///
+/// Links of configurable, bundle and grouped products are loaded on start
+/// and follow changes of their link tables in binlog.
+/// Links are tracked per table, so removal of a link from one table
+/// keeps the parent while it is still linked in another one.
+/// Parent columns of Adobe Commerce hold row version of the parent,
+/// which is resolved to its product when row id resolver is attached.
+#[derive(Clone, Default)]
+pub struct ParentIndex {
+    parents: Arc<RwLock<HashMap<usize, ParentLinks>>>,
+    row_ids: Option<RowIdResolver>,
+}
+
+impl ParentIndex {
+    pub fn with_row_id_resolver(self, row_ids: RowIdResolver) -> Self {
+        Self {
+            row_ids: Some(row_ids),
+            ..self
+        }
+    }
+
+    /// Loads existing links of all link tables from database
+    pub async fn populate<T, P>(
+        &self,
+        connection: &mut Conn,
+        database_name: T,
+        table_prefix: P,
+    ) -> Result<(), Error>
+    where
+        T: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let database_name = database_name.as_ref();
+
+        for (table, child, parent) in PARENT_LINKS {
+            let table_name = table_name_with_prefix(table, table_prefix.as_ref());
+            let links: Vec<(usize, usize)> = connection
+                .query(format!(
+                    "SELECT {child}, {parent} FROM `{database_name}`.`{table_name}`"
+                ))
+                .await?;
+
+            for (child_id, parent_id) in links {
+                self.insert(table, child_id, parent_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn insert(&self, table: &'static str, child_id: usize, parent_id: usize) {
+        if let Ok(mut parents) = self.parents.write() {
+            parents
+                .entry(child_id)
+                .or_default()
+                .insert((table, parent_id));
+        }
+    }
+
+    fn remove(&self, table: &'static str, child_id: usize, parent_id: usize) {
+        if let Ok(mut parents) = self.parents.write() {
+            if let Some(links) = parents.get_mut(&child_id) {
+                links.remove(&(table, parent_id));
+
+                if links.is_empty() {
+                    parents.remove(&child_id);
+                }
+            }
+        }
+    }
+
+    /// Parent products of the child, sorted by id
+    pub fn parents(&self, child_id: usize) -> Vec<usize> {
+        let mut parents: Vec<usize> = match self.parents.read() {
+            Ok(parents) => parents
+                .get(&child_id)
+                .into_iter()
+                .flatten()
+                .map(|(_, parent_id)| *parent_id)
+                .collect(),
+            Err(_) => return Vec::new(),
+        };
+
+        if let Some(row_ids) = self
+            .row_ids
+            .as_ref()
+            .filter(|row_ids| row_ids.is_content_staging())
+        {
+            parents = parents
+                .into_iter()
+                .filter_map(|row_id| row_ids.resolve(row_id))
+                .collect();
+        }
+
+        parents.sort();
+        parents.dedup();
+        parents
+    }
+
+    /// Checks if links are maintained from changes of the table
+    pub fn is_indexed_table(&self, table_name: &str) -> bool {
+        PARENT_LINKS
+            .into_iter()
+            .any(|(table, _, _)| table == table_name)
+    }
+
+    /// Maintains links from changes in link tables
+    pub fn apply_event(&self, event: &Event, schema: &impl TableSchema) -> Result<(), Error> {
+        let (table, child, parent) = match PARENT_LINKS
+            .into_iter()
+            .find(|(table, _, _)| *table == schema.table_name())
+        {
+            Some(link) => link,
+            None => return Ok(()),
+        };
+
+        match event {
+            Event::InsertRow(row) => {
+                self.insert(table, row.parse(child, schema)?, row.parse(parent, schema)?)
+            }
+            Event::DeleteRow(row) => {
+                self.remove(table, row.parse(child, schema)?, row.parse(parent, schema)?)
+            }
+            Event::UpdateRow(row) => {
+                self.remove(table, row.parse(child, schema)?, row.parse(parent, schema)?);
+                self.insert(
+                    table,
+                    row.parse_changed(child, schema)?,
+                    row.parse_changed(parent, schema)?,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maintains_parents_from_link_tables() {
+        let index = ParentIndex::default();
+        let super_link = test_table!(
+            "catalog_product_super_link",
+            ["link_id", "product_id", "parent_id"]
+        );
+
+        index
+            .apply_event(&Event::InsertRow(binlog_row!(1, 5, 10)), &super_link)
+            .unwrap();
+        index
+            .apply_event(&Event::InsertRow(binlog_row!(2, 5, 11)), &super_link)
+            .unwrap();
+        index
+            .apply_event(&Event::DeleteRow(binlog_row!(2, 5, 11)), &super_link)
+            .unwrap();
+
+        assert_eq!(
+            vec![index.parents(5), index.parents(6)],
+            vec![vec![10], vec![]]
+        );
+    }
+
+    #[test]
+    fn keeps_parent_linked_in_another_table() {
+        let index = ParentIndex::default();
+
+        index
+            .apply_event(
+                &Event::InsertRow(binlog_row!(5, 10)),
+                &test_table!("catalog_product_relation", ["child_id", "parent_id"]),
+            )
+            .unwrap();
+        index
+            .apply_event(
+                &Event::InsertRow(binlog_row!(1, 5, 10)),
+                &test_table!(
+                    "catalog_product_super_link",
+                    ["link_id", "product_id", "parent_id"]
+                ),
+            )
+            .unwrap();
+        index
+            .apply_event(
+                &Event::DeleteRow(binlog_row!(1, 5, 10)),
+                &test_table!(
+                    "catalog_product_super_link",
+                    ["link_id", "product_id", "parent_id"]
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(index.parents(5), vec![10]);
+    }
+
+    #[test]
+    fn maps_bundle_selection_parents() {
+        let index = ParentIndex::default();
+
+        index
+            .apply_event(
+                &Event::InsertRow(binlog_row!(1, 2, 20, 5)),
+                &test_table!(
+                    "catalog_product_bundle_selection",
+                    [
+                        "selection_id",
+                        "option_id",
+                        "parent_product_id",
+                        "product_id"
+                    ]
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(index.parents(5), vec![20]);
+    }
+
+    #[test]
+    fn resolves_parent_row_versions_with_content_staging() {
+        let row_ids = RowIdResolver::default();
+        row_ids.insert(20, 10);
+        row_ids.insert(21, 10);
+        row_ids.set_content_staging();
+
+        let index = ParentIndex::default().with_row_id_resolver(row_ids);
+        index.insert("catalog_product_super_link", 5, 20);
+        index.insert("catalog_product_relation", 5, 21);
+
+        assert_eq!(index.parents(5), vec![10]);
+    }
+
+    #[test]
+    fn keeps_parent_ids_without_content_staging() {
+        let index = ParentIndex::default().with_row_id_resolver(RowIdResolver::default());
+        index.insert("catalog_product_super_link", 5, 20);
+
+        assert_eq!(index.parents(5), vec![20]);
+    }
+}
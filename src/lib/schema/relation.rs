@@ -0,0 +1,292 @@
+use crate::error::Error;
+use crate::replication::Event;
+use crate::schema::TableSchema;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Relation of rows in a table to their owner, like option to product or address to customer
+#[derive(Debug, PartialEq)]
+pub struct Relation {
+    pub table: &'static str,
+    pub id: &'static str,
+    pub owner: &'static str,
+    /// Relation of the owner, when it is not the top level entity itself
+    pub parent: Option<&'static Relation>,
+    /// Column and its value for rows of the relation, when table holds rows of other owners as well
+    pub condition: Option<(&'static str, usize)>,
+}
+
+impl Relation {
+    /// Checks if row belongs to the relation by value of condition column
+    pub fn is_related(
+        &self,
+        value: impl FnOnce(&str) -> Result<usize, Error>,
+    ) -> Result<bool, Error> {
+        match self.condition {
+            Some((column, expected)) => Ok(value(column)? == expected),
+            None => Ok(true),
+        }
+    }
+}
+
+pub static PRODUCT_OPTION: Relation = Relation {
+    table: "catalog_product_option",
+    id: "option_id",
+    owner: "product_id",
+    parent: None,
+    condition: None,
+};
+
+pub static PRODUCT_OPTION_TYPE: Relation = Relation {
+    table: "catalog_product_option_type_value",
+    id: "option_type_id",
+    owner: "option_id",
+    parent: Some(&PRODUCT_OPTION),
+    condition: None,
+};
+
+pub static PRODUCT_SUPER_ATTRIBUTE: Relation = Relation {
+    table: "catalog_product_super_attribute",
+    id: "product_super_attribute_id",
+    owner: "product_id",
+    parent: None,
+    condition: None,
+};
+
+/// Owners of rows in related tables
+///
+/// Owner of each related row is registered when the row appears in binlog
+/// and looked up on the first change of a row created before replication started.
+/// Nested relations, like option value to option to product, are resolved level by level.
+/// Mappers register relations of their tables, so only the rows they resolve are indexed
+#[derive(Clone, Default)]
+pub struct RelationIndex {
+    owners: Arc<RwLock<HashMap<(&'static str, usize), usize>>>,
+    relations: Arc<RwLock<Vec<&'static Relation>>>,
+    tables: Arc<RwLock<HashMap<&'static str, (&'static str, &'static Relation)>>>,
+}
+
+impl RelationIndex {
+    /// Registers table referencing rows of the relation by the column, with all levels of relation
+    pub fn register(
+        &self,
+        table_name: &'static str,
+        column: &'static str,
+        relation: &'static Relation,
+    ) {
+        if let Ok(mut relations) = self.relations.write() {
+            let mut level = Some(relation);
+
+            while let Some(relation) = level {
+                if !relations.contains(&relation) {
+                    relations.push(relation);
+                }
+
+                level = relation.parent;
+            }
+        }
+
+        if let Ok(mut tables) = self.tables.write() {
+            tables.insert(table_name, (column, relation));
+        }
+    }
+
+    /// Column and relation rows of the table are referencing their owner with
+    pub fn table_relation(&self, table_name: &str) -> Option<(&'static str, &'static Relation)> {
+        self.tables.read().ok()?.get(table_name).copied()
+    }
+
+    fn relation(&self, table_name: &str) -> Option<&'static Relation> {
+        self.relations
+            .read()
+            .ok()?
+            .iter()
+            .find(|relation| relation.table == table_name)
+            .copied()
+    }
+
+    pub fn insert(&self, relation: &Relation, id: usize, owner: usize) {
+        if let Ok(mut owners) = self.owners.write() {
+            owners.insert((relation.table, id), owner);
+        }
+    }
+
+    fn owner(&self, relation: &Relation, id: usize) -> Option<usize> {
+        self.owners.read().ok()?.get(&(relation.table, id)).copied()
+    }
+
+    /// Resolves id of top level owner through all levels of relation
+    pub fn resolve(&self, relation: &Relation, id: usize) -> Option<usize> {
+        let owner = self.owner(relation, id)?;
+
+        match relation.parent {
+            Some(parent) => self.resolve(parent, owner),
+            None => Some(owner),
+        }
+    }
+
+    /// Returns first level of relation that is not known yet and needs to be looked up
+    pub fn missing(
+        &self,
+        relation: &'static Relation,
+        id: usize,
+    ) -> Option<(&'static Relation, usize)> {
+        match (self.owner(relation, id), relation.parent) {
+            (None, _) => Some((relation, id)),
+            (Some(owner), Some(parent)) => self.missing(parent, owner),
+            (Some(_), None) => None,
+        }
+    }
+
+    /// Checks if owners are registered from changes of the table
+    pub fn is_indexed_table(&self, table_name: &str) -> bool {
+        self.relation(table_name).is_some()
+    }
+
+    /// Registers owners from changes in relation tables
+    ///
+    /// Deleted rows are kept, as rows referencing them can be deleted later in the same transaction
+    pub fn apply_event(&self, event: &Event, schema: &impl TableSchema) -> Result<(), Error> {
+        let relation = match self.relation(schema.table_name()) {
+            Some(relation) => relation,
+            None => return Ok(()),
+        };
+
+        match event {
+            Event::InsertRow(row) | Event::DeleteRow(row) => {
+                if relation.is_related(|column| row.parse(column, schema))? {
+                    self.insert(
+                        relation,
+                        row.parse(relation.id, schema)?,
+                        row.parse(relation.owner, schema)?,
+                    )
+                }
+            }
+            Event::UpdateRow(row) => {
+                if relation.is_related(|column| row.parse_changed(column, schema))? {
+                    self.insert(
+                        relation,
+                        row.parse_changed(relation.id, schema)?,
+                        row.parse_changed(relation.owner, schema)?,
+                    )
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_product_of_option() {
+        let index = RelationIndex::default();
+        index.register("catalog_product_option_title", "option_id", &PRODUCT_OPTION);
+
+        index
+            .apply_event(
+                &Event::InsertRow(binlog_row!(3, 10, "drop_down")),
+                &test_table!(
+                    "catalog_product_option",
+                    ["option_id", "product_id", "type"]
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                index.resolve(&PRODUCT_OPTION, 3),
+                index.resolve(&PRODUCT_OPTION, 4)
+            ],
+            vec![Some(10), None]
+        );
+    }
+
+    #[test]
+    fn resolves_product_of_option_value_through_option() {
+        let index = RelationIndex::default();
+        index.insert(&PRODUCT_OPTION, 3, 10);
+        index.insert(&PRODUCT_OPTION_TYPE, 7, 3);
+
+        assert_eq!(index.resolve(&PRODUCT_OPTION_TYPE, 7), Some(10));
+    }
+
+    #[test]
+    fn reports_missing_level_of_relation() {
+        let index = RelationIndex::default();
+        index.insert(&PRODUCT_OPTION_TYPE, 7, 3);
+
+        assert_eq!(
+            vec![
+                index.missing(&PRODUCT_OPTION_TYPE, 7),
+                index.missing(&PRODUCT_OPTION_TYPE, 8)
+            ],
+            vec![Some((&PRODUCT_OPTION, 3)), Some((&PRODUCT_OPTION_TYPE, 8))]
+        );
+    }
+
+    #[test]
+    fn skips_rows_of_unregistered_relation() {
+        let index = RelationIndex::default();
+
+        index
+            .apply_event(
+                &Event::InsertRow(binlog_row!(3, 10, "drop_down")),
+                &test_table!(
+                    "catalog_product_option",
+                    ["option_id", "product_id", "type"]
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(
+            (
+                index.resolve(&PRODUCT_OPTION, 3),
+                index.is_indexed_table("catalog_product_option")
+            ),
+            (None, false)
+        );
+    }
+
+    #[test]
+    fn finds_registered_relation_of_tables() {
+        let index = RelationIndex::default();
+        index.register(
+            "catalog_product_option_type_price",
+            "option_type_id",
+            &PRODUCT_OPTION_TYPE,
+        );
+        index.register(
+            "catalog_product_super_attribute_label",
+            "product_super_attribute_id",
+            &PRODUCT_SUPER_ATTRIBUTE,
+        );
+
+        assert_eq!(
+            (
+                vec![
+                    index.table_relation("catalog_product_option_type_price"),
+                    index.table_relation("catalog_product_super_attribute_label"),
+                    index.table_relation("catalog_product_option"),
+                ],
+                vec![
+                    index.is_indexed_table("catalog_product_option_type_value"),
+                    index.is_indexed_table("catalog_product_option"),
+                    index.is_indexed_table("catalog_product_super_attribute"),
+                    index.is_indexed_table("customer_address_entity"),
+                ]
+            ),
+            (
+                vec![
+                    Some(("option_type_id", &PRODUCT_OPTION_TYPE)),
+                    Some(("product_super_attribute_id", &PRODUCT_SUPER_ATTRIBUTE)),
+                    None
+                ],
+                vec![true, true, true, false]
+            )
+        );
+    }
+}
@@ -0,0 +1,398 @@
+use crate::error::Error;
+use crate::replication::Event;
+use crate::schema::{table_name_with_prefix, TableSchema};
+use mysql_async::prelude::Queryable;
+use mysql_async::Conn;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Tables of custom options, configurable super attributes, composite and linked products
+///
+/// In Adobe Commerce their product or parent product column holds `row_id` of the product,
+/// directly or through the option or super attribute the row belongs to.
+/// Child and linked product columns still hold `entity_id`.
+const ROW_VERSION_RELATION_TABLES: [&str; 14] = [
+    "catalog_product_option",
+    "catalog_product_option_title",
+    "catalog_product_option_price",
+    "catalog_product_option_type_value",
+    "catalog_product_option_type_title",
+    "catalog_product_option_type_price",
+    "catalog_product_super_attribute",
+    "catalog_product_super_attribute_label",
+    "catalog_product_super_attribute_pricing",
+    "catalog_product_super_link",
+    "catalog_product_bundle_option",
+    "catalog_product_bundle_selection",
+    "catalog_product_link",
+    "catalog_product_relation",
+];
+
+/// Entities versioned by Adobe Commerce content staging, as entity table and id column
+const STAGED_ENTITIES: [(&str, &str); 4] = [
+    ("catalog_product_entity", "entity_id"),
+    ("catalogrule", "rule_id"),
+    ("cms_page", "page_id"),
+    ("cms_block", "block_id"),
+];
+
+/// Link tables of staged entities other than product, as table and entity table
+///
+/// In Adobe Commerce they reference the entity by `row_id` in place of its id column.
+const ROW_VERSION_LINK_TABLES: [(&str, &str); 4] = [
+    ("catalogrule_website", "catalogrule"),
+    ("catalogrule_customer_group", "catalogrule"),
+    ("cms_page_store", "cms_page"),
+    ("cms_block_store", "cms_block"),
+];
+
+/// Entity ids by `row_id` of Adobe Commerce content staging
+///
+/// Row versions of existing products and other staged entities are loaded on start,
+/// new ones are registered from changes of their entity table
+/// and unknown ones are looked up by row id.
+/// Versions are never forgotten, so changes of a deleted version can still be attributed
+#[derive(Clone, Default)]
+pub struct RowIdResolver {
+    entities: Arc<RwLock<HashMap<(&'static str, usize), usize>>>,
+    content_staging: Arc<AtomicBool>,
+}
+
+/// Checks if table rows belong to product row version instead of product entity
+pub fn is_row_id_table(schema: &impl TableSchema) -> bool {
+    schema.table_name().starts_with("catalog_product_")
+        && schema.table_name() != "catalog_product_entity"
+        && schema.has_column("row_id")
+        && !schema.has_column("entity_id")
+}
+
+/// Checks if product of table rows is referenced by row version under `product_id` column name
+pub fn is_row_version_relation_table(table_name: &str) -> bool {
+    ROW_VERSION_RELATION_TABLES.contains(&table_name)
+}
+
+/// Staged entity table and its id column, when table rows reference the entity by `row_id`
+pub fn row_version_entity(schema: &impl TableSchema) -> Option<(&'static str, &'static str)> {
+    let entity_table = match is_row_id_table(schema) {
+        true => "catalog_product_entity",
+        false => {
+            ROW_VERSION_LINK_TABLES
+                .into_iter()
+                .find(|(table, _)| *table == schema.table_name())?
+                .1
+        }
+    };
+
+    STAGED_ENTITIES
+        .into_iter()
+        .find(|(table, _)| *table == entity_table)
+        .filter(|(_, id)| schema.has_column("row_id") && !schema.has_column(id))
+}
+
+impl RowIdResolver {
+    /// Loads row versions of all staged entities and marks database as one with content staging
+    ///
+    /// Entities which staging module is disabled have no `row_id` column and are skipped.
+    pub async fn populate<T, P>(
+        &self,
+        connection: &mut Conn,
+        database_name: T,
+        table_prefix: P,
+    ) -> Result<(), Error>
+    where
+        T: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let database_name = database_name.as_ref();
+
+        for (table, id) in STAGED_ENTITIES {
+            let table_name = table_name_with_prefix(table, table_prefix.as_ref());
+            let exists: Option<String> = connection
+                .exec_first(
+                    "SELECT COLUMN_NAME FROM information_schema.COLUMNS \
+                     WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND COLUMN_NAME = 'row_id'",
+                    (database_name, table_name.as_ref()),
+                )
+                .await?;
+
+            if exists.is_none() {
+                continue;
+            }
+
+            let versions: Vec<(usize, usize)> = connection
+                .query(format!(
+                    "SELECT row_id, {id} FROM `{database_name}`.`{table_name}`"
+                ))
+                .await?;
+
+            for (row_id, entity_id) in versions {
+                self.insert_entity(table, row_id, entity_id);
+            }
+        }
+
+        self.set_content_staging();
+
+        Ok(())
+    }
+
+    pub fn set_content_staging(&self) {
+        self.content_staging.store(true, Ordering::Relaxed);
+    }
+
+    /// Database has content staging, so product references in relation tables are row versions
+    pub fn is_content_staging(&self) -> bool {
+        self.content_staging.load(Ordering::Relaxed)
+    }
+
+    /// Registers row version of a product
+    pub fn insert(&self, row_id: usize, entity_id: usize) {
+        self.insert_entity("catalog_product_entity", row_id, entity_id);
+    }
+
+    /// Product of the row version
+    pub fn resolve(&self, row_id: usize) -> Option<usize> {
+        self.resolve_entity("catalog_product_entity", row_id)
+    }
+
+    /// Registers row version of an entity stored in the entity table
+    pub fn insert_entity(&self, entity_table: &'static str, row_id: usize, entity_id: usize) {
+        if let Ok(mut entities) = self.entities.write() {
+            entities.insert((entity_table, row_id), entity_id);
+        }
+    }
+
+    /// Entity of the row version stored in the entity table
+    pub fn resolve_entity(&self, entity_table: &'static str, row_id: usize) -> Option<usize> {
+        self.entities
+            .read()
+            .ok()?
+            .get(&(entity_table, row_id))
+            .copied()
+    }
+
+    /// Checks if row versions are registered from changes of the table
+    pub fn is_indexed_table(&self, table_name: &str) -> bool {
+        STAGED_ENTITIES
+            .into_iter()
+            .any(|(table, _)| table == table_name)
+    }
+
+    /// Registers row versions from changes of staged entity tables
+    ///
+    /// Deleted row versions are kept, as rows referencing them can be deleted later in the same transaction
+    pub fn apply_event(&self, event: &Event, schema: &impl TableSchema) -> Result<(), Error> {
+        let (table, id) = match STAGED_ENTITIES
+            .into_iter()
+            .find(|(table, _)| *table == schema.table_name())
+        {
+            Some(entity) if schema.has_column("row_id") => entity,
+            _ => return Ok(()),
+        };
+
+        match event {
+            Event::InsertRow(row) | Event::DeleteRow(row) => {
+                self.insert_entity(table, row.parse("row_id", schema)?, row.parse(id, schema)?)
+            }
+            Event::UpdateRow(row) => self.insert_entity(
+                table,
+                row.parse_changed("row_id", schema)?,
+                row.parse_changed(id, schema)?,
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Returns row ids of the event that are not known yet and need to be looked up
+    pub fn unresolved(
+        &self,
+        event: &Event,
+        schema: &impl TableSchema,
+    ) -> Result<Vec<usize>, Error> {
+        let (entity_table, _) = match row_version_entity(schema) {
+            Some(entity) => entity,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut row_ids: Vec<usize> = match event {
+            Event::InsertRow(row) | Event::DeleteRow(row) => vec![row.parse("row_id", schema)?],
+            Event::UpdateRow(row) => vec![
+                row.parse("row_id", schema)?,
+                row.parse_changed("row_id", schema)?,
+            ],
+        };
+
+        row_ids.dedup();
+        row_ids.retain(|row_id| self.resolve_entity(entity_table, *row_id).is_none());
+
+        Ok(row_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    #[test]
+    fn registers_row_versions_of_product() {
+        let resolver = RowIdResolver::default();
+
+        resolver
+            .apply_event(
+                &Event::InsertRow(binlog_row!(10, 1, 1)),
+                &test_table!(
+                    "catalog_product_entity",
+                    ["row_id", "entity_id", "created_in"]
+                ),
+            )
+            .unwrap();
+        resolver
+            .apply_event(
+                &Event::InsertRow(binlog_row!(11, 1, 1700000000)),
+                &test_table!(
+                    "catalog_product_entity",
+                    ["row_id", "entity_id", "created_in"]
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                resolver.resolve(10),
+                resolver.resolve(11),
+                resolver.resolve(12)
+            ],
+            vec![Some(1), Some(1), None]
+        );
+    }
+
+    #[test]
+    fn detects_relation_tables_referencing_row_versions() {
+        assert_eq!(
+            vec![
+                is_row_version_relation_table("catalog_product_option"),
+                is_row_version_relation_table("catalog_product_super_attribute_label"),
+                is_row_version_relation_table("catalog_product_super_link"),
+                is_row_version_relation_table("catalog_product_bundle_selection"),
+                is_row_version_relation_table("catalog_product_website"),
+            ],
+            vec![true, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn ignores_product_table_without_row_id() {
+        let resolver = RowIdResolver::default();
+
+        resolver
+            .apply_event(
+                &Event::InsertRow(binlog_row!(10, 1)),
+                &test_table!("catalog_product_entity", ["entity_id", "attribute_set_id"]),
+            )
+            .unwrap();
+
+        assert_eq!(resolver.resolve(10), None);
+    }
+
+    #[test]
+    fn reports_unresolved_row_ids_of_row_version_tables() {
+        let resolver = RowIdResolver::default();
+        resolver.insert(10, 1);
+
+        assert_eq!(
+            resolver
+                .unresolved(
+                    &Event::UpdateRow(UpdateRowEvent::new(
+                        binlog_row!(10, 73, 0, "one"),
+                        binlog_row!(11, 73, 0, "one")
+                    )),
+                    &test_table!(
+                        "catalog_product_entity_varchar",
+                        ["row_id", "attribute_id", "store_id", "value"]
+                    )
+                )
+                .unwrap(),
+            vec![11]
+        );
+    }
+
+    #[test]
+    fn registers_row_versions_of_catalog_rule() {
+        let resolver = RowIdResolver::default();
+
+        resolver
+            .apply_event(
+                &Event::InsertRow(binlog_row!(7, 3, "Summer sale")),
+                &test_table!("catalogrule", ["row_id", "rule_id", "name"]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            (
+                resolver.resolve_entity("catalogrule", 7),
+                resolver.resolve(7)
+            ),
+            (Some(3), None)
+        );
+    }
+
+    #[test]
+    fn reports_unresolved_row_ids_of_catalog_rule_links() {
+        let resolver = RowIdResolver::default();
+        resolver.insert(7, 1);
+
+        assert_eq!(
+            resolver
+                .unresolved(
+                    &Event::InsertRow(binlog_row!(7, 1)),
+                    &test_table!("catalogrule_website", ["row_id", "website_id"])
+                )
+                .unwrap(),
+            vec![7]
+        );
+    }
+
+    #[test]
+    fn detects_entity_referenced_by_row_version() {
+        assert_eq!(
+            vec![
+                row_version_entity(&test_table!(
+                    "catalog_product_entity_int",
+                    ["row_id", "attribute_id", "store_id", "value"]
+                )),
+                row_version_entity(&test_table!(
+                    "catalogrule_website",
+                    ["row_id", "website_id"]
+                )),
+                row_version_entity(&test_table!(
+                    "catalogrule_website",
+                    ["rule_id", "website_id"]
+                )),
+                row_version_entity(&test_table!("cms_block_store", ["row_id", "store_id"])),
+            ],
+            vec![
+                Some(("catalog_product_entity", "entity_id")),
+                Some(("catalogrule", "rule_id")),
+                None,
+                Some(("cms_block", "block_id")),
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_resolve_tables_with_entity_id() {
+        let resolver = RowIdResolver::default();
+
+        assert_eq!(
+            resolver
+                .unresolved(
+                    &Event::InsertRow(binlog_row!(10, 1)),
+                    &test_table!("catalog_product_website", ["product_id", "website_id"])
+                )
+                .unwrap(),
+            Vec::<usize>::new()
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::error::Error;
+use crate::replication::Event;
+use crate::schema::{table_name_with_prefix, TableSchema};
+use mysql_async::prelude::Queryable;
+use mysql_async::Conn;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Store tables of Magento 2 and Magento 1 / OpenMage
+const STORE_TABLES: [&str; 2] = ["store", "core_store"];
+
+/// Admin store that represents values for all stores
+const ADMIN_STORE_ID: usize = 0;
+
+/// Store views with the website they belong to
+///
+/// Loaded from `store` or `core_store` table and kept in sync with its changes,
+/// so a value of admin store can be reported for every store view that inherits it.
+#[derive(Clone, Default)]
+pub struct StoreIndex {
+    stores: Arc<RwLock<HashMap<usize, usize>>>,
+}
+
+impl StoreIndex {
+    /// Loads existing stores from the store table of the platform
+    pub async fn populate<T, P>(
+        &self,
+        connection: &mut Conn,
+        database_name: T,
+        table_prefix: P,
+    ) -> Result<(), Error>
+    where
+        T: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let database_name = database_name.as_ref();
+
+        for table in STORE_TABLES {
+            let table_name = table_name_with_prefix(table, table_prefix.as_ref());
+            let exists: Option<String> = connection
+                .exec_first(
+                    "SELECT TABLE_NAME FROM information_schema.TABLES \
+                     WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?",
+                    (database_name, table_name.as_ref()),
+                )
+                .await?;
+
+            if exists.is_none() {
+                continue;
+            }
+
+            let stores: Vec<(usize, usize)> = connection
+                .query(format!(
+                    "SELECT store_id, website_id FROM `{database_name}`.`{table_name}`"
+                ))
+                .await?;
+
+            for (store_id, website_id) in stores {
+                self.insert(store_id, website_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn insert(&self, store_id: usize, website_id: usize) {
+        if let Ok(mut stores) = self.stores.write() {
+            stores.insert(store_id, website_id);
+        }
+    }
+
+    fn remove(&self, store_id: usize) {
+        if let Ok(mut stores) = self.stores.write() {
+            stores.remove(&store_id);
+        }
+    }
+
+    /// Store views without admin store, sorted by id
+    pub fn stores(&self) -> Vec<usize> {
+        let mut stores: Vec<usize> = match self.stores.read() {
+            Ok(stores) => stores
+                .keys()
+                .copied()
+                .filter(|store_id| *store_id != ADMIN_STORE_ID)
+                .collect(),
+            Err(_) => return Vec::new(),
+        };
+
+        stores.sort();
+        stores
+    }
+
+    /// Store views of the website, sorted by id
+    pub fn website_stores(&self, website_id: usize) -> Vec<usize> {
+        let mut stores: Vec<usize> = match self.stores.read() {
+            Ok(stores) => stores
+                .iter()
+                .filter(|(store_id, website)| {
+                    **store_id != ADMIN_STORE_ID && **website == website_id
+                })
+                .map(|(store_id, _)| *store_id)
+                .collect(),
+            Err(_) => return Vec::new(),
+        };
+
+        stores.sort();
+        stores
+    }
+
+    /// Store with store views that inherit its values
+    ///
+    /// Values of admin store apply to every store view without own value,
+    /// so changes scoped by admin store are expanded into all store views.
+    pub fn expand(&self, store_id: usize) -> Vec<usize> {
+        match store_id {
+            ADMIN_STORE_ID => [ADMIN_STORE_ID].into_iter().chain(self.stores()).collect(),
+            store_id => vec![store_id],
+        }
+    }
+
+    /// Checks if stores are maintained from changes of the table
+    pub fn is_indexed_table(&self, table_name: &str) -> bool {
+        STORE_TABLES.contains(&table_name)
+    }
+
+    /// Maintains stores from changes in store table
+    pub fn apply_event(&self, event: &Event, schema: &impl TableSchema) -> Result<(), Error> {
+        if !STORE_TABLES.contains(&schema.table_name()) {
+            return Ok(());
+        }
+
+        match event {
+            Event::InsertRow(row) => self.insert(
+                row.parse("store_id", schema)?,
+                row.parse("website_id", schema)?,
+            ),
+            Event::DeleteRow(row) => self.remove(row.parse("store_id", schema)?),
+            Event::UpdateRow(row) => {
+                self.remove(row.parse("store_id", schema)?);
+                self.insert(
+                    row.parse_changed("store_id", schema)?,
+                    row.parse_changed("website_id", schema)?,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replication::UpdateRowEvent;
+
+    fn index() -> StoreIndex {
+        let index = StoreIndex::default();
+        index.insert(0, 0);
+        index.insert(1, 1);
+        index.insert(2, 1);
+        index.insert(3, 2);
+        index
+    }
+
+    #[test]
+    fn lists_stores_without_admin_store() {
+        let index = index();
+
+        assert_eq!(
+            (index.stores(), index.website_stores(1)),
+            (vec![1, 2, 3], vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn expands_admin_store_into_store_views() {
+        let index = index();
+
+        assert_eq!(
+            (index.expand(0), index.expand(2)),
+            (vec![0, 1, 2, 3], vec![2])
+        );
+    }
+
+    #[test]
+    fn maintains_stores_from_store_table_changes() {
+        let index = index();
+        let schema = test_table!("store", ["store_id", "code", "website_id"]);
+
+        index
+            .apply_event(&Event::InsertRow(binlog_row!(4, "nl", 2)), &schema)
+            .unwrap();
+        index
+            .apply_event(&Event::DeleteRow(binlog_row!(1, "default", 1)), &schema)
+            .unwrap();
+        index
+            .apply_event(
+                &Event::UpdateRow(UpdateRowEvent::new(
+                    binlog_row!(2, "en", 1),
+                    binlog_row!(2, "en", 2),
+                )),
+                &schema,
+            )
+            .unwrap();
+
+        assert_eq!(
+            (index.stores(), index.website_stores(2)),
+            (vec![2, 3, 4], vec![2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn ignores_other_tables() {
+        let index = StoreIndex::default();
+
+        index
+            .apply_event(
+                &Event::InsertRow(binlog_row!(4, "nl", 2)),
+                &test_table!("store_group", ["group_id", "code", "website_id"]),
+            )
+            .unwrap();
+
+        assert_eq!(index.stores(), Vec::<usize>::new());
+    }
+}
@@ -123,6 +123,28 @@ macro_rules! mapper_test {
     };
 }
 
+#[cfg(test)]
+macro_rules! mapper_changes_test {
+    ($mapper:expr, $expected:expr, update[($($before:expr),+), ($($after:expr),+)], [$($column:expr),+]) => {
+        let table = test_table![$($column),+];
+        let event = $crate::replication::Event::UpdateRow($crate::replication::UpdateRowEvent::new(
+            binlog_row!($($before),+),
+            binlog_row!($($after),+),
+        ));
+        mapper_changes_test!($mapper, $expected, event, table);
+    };
+    ($mapper:expr, $expected:expr, $event:expr, $table:expr) => {
+        let mut changes = Vec::new();
+        $crate::mapper::ChangeLogMapper::map_event_into(
+                &$mapper,
+                &$event,
+                &$table,
+                &mut changes
+            ).unwrap();
+        assert_eq!(changes, $expected);
+    };
+}
+
 macro_rules! output_test {
     ($formatter:expr, $aggregate:expr, $expected:expr) => {
         let mut buffer = Cursor::new(Vec::new());
@@ -3,5 +3,11 @@ use mage_os_database_changelog::error::Error;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    Application::new().run(command_from_cli()).await
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let command = command_from_cli();
+
+    Application::new().run(command).await
 }